@@ -0,0 +1,122 @@
+//! Components skip re-rendering while their props stay the same. A component inserted with
+//! [`Component::memo`] is only rendered when the previous rebuild had no component of the same
+//! type at the same key, or its props differ, otherwise the DOM it produced last time is kept
+//! as it is:
+//!
+//! ```ignore
+//! struct TodoItem;
+//!
+//! impl Component for TodoItem {
+//!     type Props = (u32, String, bool);
+//!
+//!     fn render((id, title, done): &Self::Props) -> ElementBuilder {
+//!         "li".class_if("done", *done).text(title)
+//!     }
+//! }
+//!
+//! "ul".children(todos.iter().map(|todo| {
+//!     TodoItem::memo((todo.id, todo.title.clone(), todo.done)).key(todo.id)
+//! }))
+//! ```
+//!
+//! Props have to hold everything `render` reads, including event handlers' captures.
+
+use crate::element_builder::{AsElementBuilder, ElementBuilder, ElementWithChildrenRecipe};
+use std::{
+    any::{Any, TypeId},
+    rc::Rc,
+};
+
+pub trait Component: 'static {
+    type Props: PartialEq + 'static;
+
+    fn render(props: &Self::Props) -> ElementBuilder;
+
+    /// A child rendered lazily, only when `props` differ from the previous rebuild. `key` is the
+    /// only builder method that applies to the returned builder, it identifies the component
+    /// among its siblings.
+    fn memo(props: Self::Props) -> ElementBuilder
+    where
+        Self: Sized,
+    {
+        let props = Rc::new(props);
+        let memo = Memo {
+            component: TypeId::of::<Self>(),
+            name: std::any::type_name::<Self>(),
+            props: props.clone(),
+            eq: |previous, next| {
+                previous.downcast_ref::<Self::Props>() == next.downcast_ref::<Self::Props>()
+            },
+        };
+        ElementBuilder::deferred(Deferred {
+            memo,
+            render: Box::new(move || Self::render(&props)),
+        })
+    }
+}
+
+/// Type and props of a rendered component, kept in the snapshot to compare with the next
+/// rebuild.
+#[derive(Clone)]
+pub struct Memo {
+    pub(crate) component: TypeId,
+    name: &'static str,
+    props: Rc<dyn Any>,
+    eq: fn(&dyn Any, &dyn Any) -> bool,
+}
+
+impl Memo {
+    /// Same component with equal props.
+    pub fn same(&self, other: &Self) -> bool {
+        self.component == other.component && (self.eq)(self.props.as_ref(), other.props.as_ref())
+    }
+
+    /// Type name of the component.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl std::fmt::Debug for Memo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Memo").field(&self.name).finish()
+    }
+}
+
+pub(crate) struct Deferred {
+    pub(crate) memo: Memo,
+    pub(crate) render: Box<dyn FnOnce() -> ElementBuilder>,
+}
+
+impl Deferred {
+    pub(crate) fn into_recipe(self, key: Option<u64>) -> MemoRecipe {
+        let Self { memo, render } = self;
+        MemoRecipe {
+            key,
+            memo,
+            render: Box::new(move || {
+                let mut recipe = render().build();
+                // the snapshot has to be found under the component's key on the next rebuild
+                recipe.element.key = key;
+                recipe
+            }),
+        }
+    }
+}
+
+/// A component which still has to be rendered, unless the executor can reuse what it rendered
+/// on the previous rebuild.
+pub struct MemoRecipe {
+    pub key: Option<u64>,
+    pub memo: Memo,
+    pub render: Box<dyn FnOnce() -> ElementWithChildrenRecipe>,
+}
+
+impl std::fmt::Debug for MemoRecipe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoRecipe")
+            .field("key", &self.key)
+            .field("memo", &self.memo)
+            .finish_non_exhaustive()
+    }
+}
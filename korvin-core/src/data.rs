@@ -3,6 +3,9 @@ pub use attribute_value::AttributeValue;
 pub use element_id::ElementId;
 pub use event::KorvinClosure;
 pub use event_listener::EventListenerWrapper;
+pub use event_tasks::EventTasks;
+pub use property_name::PropertyName;
+pub use property_value::PropertyValue;
 pub use tag_name::TagName;
 pub use value::Value;
 
@@ -11,5 +14,8 @@ pub mod attribute_value;
 pub mod element_id;
 pub mod event;
 pub mod event_listener;
+pub mod event_tasks;
+pub mod property_name;
+pub mod property_value;
 pub mod tag_name;
 pub mod value;
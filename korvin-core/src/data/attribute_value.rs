@@ -1,6 +1,6 @@
 use super::Value;
 
-#[derive(PartialEq, Debug, Clone, derive_more::Display, Eq, Hash, PartialOrd)]
+#[derive(PartialEq, Debug, Clone, derive_more::Display, Eq, Hash, PartialOrd, Ord)]
 pub struct AttributeValue(Value);
 
 impl AsRef<str> for AttributeValue {
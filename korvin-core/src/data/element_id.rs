@@ -1,9 +1,21 @@
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
 use web_sys::Element;
 
-#[derive(PartialEq, Clone)]
-pub struct ElementId(Rc<Element>);
+use crate::stats::{Counter, LiveToken};
+
+#[derive(Clone)]
+pub struct ElementId {
+    element: Rc<Element>,
+    /// present only for elements created by the runtime
+    live: Option<Rc<LiveToken>>,
+}
+
+impl PartialEq for ElementId {
+    fn eq(&self, other: &Self) -> bool {
+        self.element.eq(&other.element)
+    }
+}
 
 impl std::fmt::Debug for ElementId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -16,12 +28,25 @@ impl ElementId {
         Self::new(element.into())
     }
     pub fn new(element: Element) -> Self {
-        Self(Rc::new(element))
+        Self {
+            element: Rc::new(element),
+            live: None,
+        }
+    }
+    /// element created by the runtime, counted in [`crate::stats::ThreadStats::live_elements`]
+    pub(crate) fn created(element: Element) -> Self {
+        Self {
+            element: Rc::new(element),
+            live: Some(Rc::new(LiveToken::new(Counter::Elements))),
+        }
+    }
+    pub(crate) fn live(&self) -> Option<Weak<LiveToken>> {
+        self.live.as_ref().map(Rc::downgrade)
     }
 }
 
 impl AsRef<Element> for ElementId {
     fn as_ref(&self) -> &Element {
-        self.0.as_ref()
+        self.element.as_ref()
     }
 }
@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::{
+    rc::{Rc, Weak},
+    sync::Arc,
+};
 
 use js_sys::Function;
 pub use wasm_bindgen::closure::IntoWasmClosure;
@@ -16,10 +19,12 @@ impl AsRef<str> for EventName {
 pub type WebSysClosure<Args> = Closure<dyn FnMut(Args)>;
 
 use super::Value;
+use crate::stats::{Counter, LiveToken};
 
 pub struct KorvinClosure<EventKind> {
     pub hash: u64,
     pub closure: Arc<WebSysClosure<EventKind>>,
+    live: Rc<LiveToken>,
 }
 
 impl<E> std::hash::Hash for KorvinClosure<E> {
@@ -30,15 +35,30 @@ impl<E> std::hash::Hash for KorvinClosure<E> {
 
 impl<E> std::clone::Clone for KorvinClosure<E> {
     fn clone(&self) -> Self {
-        let Self { hash, closure } = self;
+        let Self {
+            hash,
+            closure,
+            live,
+        } = self;
         Self {
             hash: *hash,
             closure: Arc::clone(closure),
+            live: Rc::clone(live),
         }
     }
 }
 
 impl<E> KorvinClosure<E> {
+    pub fn new(hash: u64, closure: WebSysClosure<E>) -> Self {
+        Self {
+            hash,
+            closure: Arc::new(closure),
+            live: Rc::new(LiveToken::new(Counter::Closures)),
+        }
+    }
+    pub(crate) fn live(&self) -> Weak<LiveToken> {
+        Rc::downgrade(&self.live)
+    }
     pub fn js_function(&self) -> &Function {
         self.closure.as_ref().as_ref().unchecked_ref()
     }
@@ -0,0 +1,83 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    future::Future,
+    rc::Rc,
+};
+
+use futures::future::{AbortHandle, Abortable};
+use web_sys::{AbortController, AbortSignal};
+
+struct RunningTask {
+    abort_handle: AbortHandle,
+    abort_controller: AbortController,
+}
+
+impl RunningTask {
+    fn abort(self) {
+        self.abort_controller.abort();
+        self.abort_handle.abort();
+    }
+}
+
+/// Futures spawned by an async event listener.
+///
+/// It is owned by the listener's closure, so it gets dropped together with it - either when
+/// the element is removed or when the listener is replaced by one with a different key.
+/// Dropping it aborts every task that is still running: the [`AbortSignal`] handed to the task
+/// fires and the future itself is dropped on its next poll.
+#[derive(Default)]
+pub struct EventTasks {
+    next_id: Cell<u64>,
+    running: RefCell<BTreeMap<u64, RunningTask>>,
+}
+
+impl EventTasks {
+    pub fn spawn<Fut>(self: &Rc<Self>, task: impl FnOnce(AbortSignal) -> Fut)
+    where
+        Fut: Future<Output = ()> + 'static,
+    {
+        let abort_controller = match AbortController::new() {
+            Ok(abort_controller) => abort_controller,
+            Err(message) => {
+                tracing::error!(
+                    ?message,
+                    "creating AbortController failed, task not spawned"
+                );
+                return;
+            }
+        };
+        let id = self.next_id.get();
+        self.next_id.set(id.wrapping_add(1));
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let future = Abortable::new(task(abort_controller.signal()), abort_registration);
+        self.running.borrow_mut().insert(
+            id,
+            RunningTask {
+                abort_handle,
+                abort_controller,
+            },
+        );
+        let tasks = Rc::downgrade(self);
+        wasm_bindgen_futures::spawn_local(async move {
+            if future.await.is_err() {
+                tracing::trace!(id, "event task aborted");
+            }
+            if let Some(tasks) = tasks.upgrade() {
+                tasks.running.borrow_mut().remove(&id);
+            }
+        });
+    }
+
+    pub fn running(&self) -> usize {
+        self.running.borrow().len()
+    }
+}
+
+impl Drop for EventTasks {
+    fn drop(&mut self) {
+        std::mem::take(self.running.get_mut())
+            .into_values()
+            .for_each(RunningTask::abort);
+    }
+}
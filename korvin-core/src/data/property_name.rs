@@ -0,0 +1,19 @@
+use super::Value;
+
+#[derive(PartialEq, Debug, Clone, derive_more::Display, PartialOrd, Ord, Eq, Hash)]
+pub struct PropertyName(Value);
+
+impl<T> From<T> for PropertyName
+where
+    T: Into<Value>,
+{
+    fn from(value: T) -> Self {
+        Self(value.into())
+    }
+}
+
+impl AsRef<str> for PropertyName {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
@@ -0,0 +1,128 @@
+use crate::element_builder::calculate_hash;
+use std::hash::Hash;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Value of a DOM property. Values are diffed by `key` instead of comparing the `JsValue`
+/// itself: primitives are keyed by their contents, objects by their identity (like
+/// `Object.is`) unless given a key through [`PropertyValue::keyed`]. The property is set again
+/// whenever the key changes.
+#[derive(Debug, Clone)]
+pub struct PropertyValue {
+    key: u64,
+    value: JsValue,
+}
+
+impl PropertyValue {
+    pub fn keyed(key: impl Hash, value: impl Into<JsValue>) -> Self {
+        Self {
+            key: calculate_hash(&key),
+            value: value.into(),
+        }
+    }
+
+    pub fn value(&self) -> &JsValue {
+        &self.value
+    }
+}
+
+impl PartialEq for PropertyValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for PropertyValue {}
+
+impl Hash for PropertyValue {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key.hash(state)
+    }
+}
+
+impl PartialOrd for PropertyValue {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PropertyValue {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// The same object always gets the same key, without keeping it alive.
+struct ObjectKeys {
+    keys: js_sys::WeakMap,
+    last: std::cell::Cell<u64>,
+}
+
+impl ObjectKeys {
+    fn key(&self, value: &JsValue) -> u64 {
+        let fresh = || {
+            self.last.set(self.last.get() + 1);
+            self.last.get()
+        };
+        if !(value.is_object() || value.is_function()) {
+            // symbols and bigints can't be weak map keys, they are never equal to anything
+            return fresh();
+        }
+        let object = value.unchecked_ref::<js_sys::Object>();
+        match self.keys.get(object).as_f64() {
+            Some(key) => key as u64,
+            None => {
+                let key = fresh();
+                self.keys.set(object, &JsValue::from(key as f64));
+                key
+            }
+        }
+    }
+}
+
+thread_local! {
+    static OBJECT_KEYS: ObjectKeys = ObjectKeys {
+        keys: js_sys::WeakMap::new(),
+        last: Default::default(),
+    };
+}
+
+/// Primitives are keyed by their content, objects (previous values read back from the DOM
+/// included) by their identity.
+impl From<JsValue> for PropertyValue {
+    fn from(value: JsValue) -> Self {
+        let key = if let Some(string) = value.as_string() {
+            calculate_hash(&("string", string))
+        } else if let Some(number) = value.as_f64() {
+            calculate_hash(&("number", number.to_bits()))
+        } else if let Some(boolean) = value.as_bool() {
+            calculate_hash(&("bool", boolean))
+        } else if value.is_null() || value.is_undefined() {
+            calculate_hash(&("nullish", value.is_null()))
+        } else {
+            calculate_hash(&("object", OBJECT_KEYS.with(|keys| keys.key(&value))))
+        };
+        Self { key, value }
+    }
+}
+
+macro_rules! primitive_property_values {
+    ($($primitive:ty => $convert:expr),* $(,)?) => {
+        $(
+            impl From<$primitive> for PropertyValue {
+                fn from(value: $primitive) -> Self {
+                    Self::from(JsValue::from($convert(value)))
+                }
+            }
+        )*
+    };
+}
+
+primitive_property_values! {
+    bool => std::convert::identity,
+    f64 => std::convert::identity,
+    f32 => f64::from,
+    i32 => f64::from,
+    u32 => f64::from,
+    &str => std::convert::identity,
+    String => std::convert::identity,
+}
@@ -1,6 +1,10 @@
 use crate::{
+    component::{Memo, MemoRecipe},
     data::{ElementId, TagName},
-    element_builder::{ElementRecipe, ElementWithChildrenRecipe},
+    element_builder::{
+        value_cache::{self, SharedValueCache},
+        ChildRecipe, ElementRecipe, ElementWithChildrenRecipe,
+    },
     mutation::{
         element::builder_mutation::{
             marker::finish::ElementFinishMutationLog,
@@ -16,12 +20,20 @@ use crate::{
     RuntimeError, RuntimeResult,
 };
 use itertools::Itertools;
-use std::collections::{BTreeMap, HashMap};
+use recycle::ElementPool;
+use std::{
+    any::TypeId,
+    collections::{BTreeMap, HashMap},
+};
+use template::Templates;
 use tracing::trace_span;
 
 #[derive(Clone, Debug)]
 pub struct DomExecutor {
     pub executed: Option<ElementWithChildrenSnapshot>,
+    pub(crate) value_cache: SharedValueCache,
+    pub(crate) element_pool: ElementPool,
+    pub(crate) templates: Templates,
 }
 
 #[derive(Debug, Clone)]
@@ -47,11 +59,14 @@ pub struct ElementSnapshot {
 pub struct ElementWithChildrenSnapshot {
     pub element: ElementSnapshot,
     pub children: Vec<Self>,
+    /// Set when the element is the root of what a memoized component rendered.
+    pub memo: Option<Memo>,
 }
 
 impl DomExecutor {
     pub fn new(current_root: ElementId) -> Self {
         let kind = TagName::from(current_root.as_ref().tag_name());
+        controlled::track_composition();
 
         Self {
             executed: Some(ElementWithChildrenSnapshot {
@@ -73,14 +88,28 @@ impl DomExecutor {
                     },
                 },
                 children: Default::default(),
+                memo: None,
             }),
+            value_cache: Default::default(),
+            element_pool: Default::default(),
+            templates: Default::default(),
         }
     }
 
     #[tracing::instrument(skip(self, new_mutations), level = "trace")]
     pub fn rebuild(&mut self, new_mutations: ElementWithChildrenRecipe) -> RuntimeResult<()> {
-        crate::element_builder::value_cache::VALUE_CACHE
-            .with(|value_cache| value_cache.borrow_mut().next_rebuild());
+        // memoized components render during the rebuild
+        let value_cache = self.value_cache.clone();
+        let rebuilt = value_cache::with_cache(&value_cache, || self.rebuild_with(new_mutations));
+        #[cfg(debug_assertions)]
+        duplicate_keys::flush();
+        value_cache.borrow_mut().next_rebuild();
+        // builders made outside `Runtime::build` used the default cache
+        value_cache::next_default_rebuild();
+        rebuilt
+    }
+
+    fn rebuild_with(&mut self, new_mutations: ElementWithChildrenRecipe) -> RuntimeResult<()> {
         let do_not_move = crate::DOCUMENT // TODO: this could be done once per rebuild
             .with(|document| document.active_element())
             .map(ElementId::new);
@@ -102,12 +131,30 @@ impl DomExecutor {
                             .collect(),
                         finish: old.element.finish.mutation.clone(),
                     },
-                    children: vec![new_mutations],
+                    children: vec![ChildRecipe::Element(new_mutations)],
+                    template: false,
                 };
-                old.rebuild(current_root, new, do_not_move)
+                old.rebuild(
+                    current_root,
+                    new,
+                    do_not_move,
+                    &mut self.element_pool,
+                    &mut self.templates,
+                )
             })
             .map(|new_snapshot| {
                 let _ = self.executed.insert(new_snapshot);
+                let cached_values = self.value_cache.borrow().len();
+                let element_pool = self.element_pool.stats();
+                let templates = self.templates.stats();
+                tracing::debug!(
+                    stats = ?crate::stats::current(cached_values, element_pool, templates),
+                    thread = ?crate::stats::thread(),
+                    "rebuild finished"
+                );
+                if crate::stats::leak_check_enabled() {
+                    crate::stats::leaked_closures();
+                }
             })
     }
 }
@@ -126,11 +173,25 @@ where
         .map_err(RuntimeError::Mutation)
 }
 
+mod controlled;
+#[cfg(debug_assertions)]
+mod duplicate_keys;
+pub mod recycle;
 pub mod reorder_children;
+pub mod template;
 
+/// Builds the subtree detached from the document, its children are appended to it as they are
+/// finished, and the caller inserts the root once.
 fn build_new_child(
     root_element: ElementId,
-    ElementWithChildrenRecipe {
+    recipe: ElementWithChildrenRecipe,
+    pool: &mut ElementPool,
+    templates: &mut Templates,
+) -> RuntimeResult<ElementWithChildrenSnapshot> {
+    if recipe.template {
+        return template::build(recipe, templates);
+    }
+    let ElementWithChildrenRecipe {
         element:
             ElementRecipe {
                 key,
@@ -139,16 +200,45 @@ fn build_new_child(
                 finish,
             },
         children,
-    }: ElementWithChildrenRecipe,
-) -> RuntimeResult<ElementWithChildrenSnapshot> {
-    let create = perform(create, root_element)?;
+        template: _,
+    } = recipe;
+    let create = match pool.take(&create.kind) {
+        Some(element_id) => SnapshotEntryV2 {
+            log: ElementCreateMutationLog {
+                kind: create.kind.clone(),
+                element_id,
+            },
+            mutation: create,
+        },
+        None => perform(create, root_element)?,
+    };
+    #[cfg(debug_assertions)]
+    duplicate_keys::warn(&create.log.element_id, &children);
     let modify = modify
         .into_iter()
         .map(|mutation| perform(mutation, create.log.element_id.clone()))
         .collect::<Result<_, _>>()?;
     let children = children
         .into_iter()
-        .map(|child| build_new_child(create.log.element_id.clone(), child))
+        .map(|child| {
+            match child {
+                ChildRecipe::Element(child) => {
+                    build_new_child(create.log.element_id.clone(), child, pool, templates)
+                }
+                ChildRecipe::Memo(MemoRecipe { memo, render, .. }) => {
+                    build_new_child(create.log.element_id.clone(), render(), pool, templates)
+                        .map(|child| child.with_memo(Some(memo)))
+                }
+            }
+            .and_then(|child| {
+                raw_operations::insert_element(
+                    child.element.create.log.element_id.clone(),
+                    create.log.element_id.clone(),
+                )
+                .map_err(RuntimeError::InsertingNewChild)
+                .map(|_| child)
+            })
+        })
         .collect::<Result<_, _>>()?;
     let finish = perform(finish, create.log.element_id.clone())?;
     Ok(ElementWithChildrenSnapshot {
@@ -159,19 +249,52 @@ fn build_new_child(
             finish,
         },
         children,
+        memo: None,
     })
 }
+/// A new child with what it will be built from.
+enum Matched {
+    /// memoized with the same props, kept as is
+    Unchanged(ElementWithChildrenSnapshot),
+    Recipe {
+        old: Option<ElementWithChildrenSnapshot>,
+        new: ElementWithChildrenRecipe,
+        memo: Option<Memo>,
+    },
+}
+
 impl ElementWithChildrenSnapshot {
+    fn with_memo(self, memo: Option<Memo>) -> Self {
+        Self { memo, ..self }
+    }
+
+    /// A reused subtree performs no mutations, but its controlled values can still have
+    /// drifted from the recipe.
+    fn resync_controlled(&self, do_not_move: Option<&ElementId>) -> RuntimeResult<()> {
+        let element_id = &self.element.create.log.element_id;
+        self.element
+            .modify
+            .iter()
+            .try_for_each(|applied| controlled::resync(&applied.mutation, element_id, do_not_move))
+            .and_then(|()| {
+                self.children
+                    .iter()
+                    .try_for_each(|child| child.resync_controlled(do_not_move))
+            })
+    }
+
     #[tracing::instrument(skip(self, recipe), level = "trace")]
     pub fn rebuild(
         self,
         current_root: ElementId,
         recipe: ElementWithChildrenRecipe,
         do_not_move: Option<ElementId>,
+        pool: &mut ElementPool,
+        templates: &mut Templates,
     ) -> RuntimeResult<Self> {
         let create = match self.element.create.mutation.eq(&recipe.element.create) {
             true => self.element.create,
-            false => return build_new_child(current_root, recipe),
+            false => return build_new_child(current_root, recipe, pool, templates),
         };
         let root_element = create.log.element_id.clone();
         let modify = {
@@ -188,6 +311,17 @@ impl ElementWithChildrenSnapshot {
                 .into_iter()
                 .map(|new| (previous_snapshots.remove(&new), new))
                 .collect_vec();
+            // a changed value replaces the old one in place instead of being reverted and set
+            // again, so that it goes through the same caret and IME handling as a resync
+            let is_value = |m: &ElementBuilderModifyMutation| {
+                matches!(m, ElementBuilderModifyMutation::SetInputValue(_))
+            };
+            let mut replaced_value = already_performed
+                .iter()
+                .any(|(applied, new)| applied.is_none() && is_value(new))
+                .then(|| previous_snapshots.keys().find(|old| is_value(old)).cloned())
+                .flatten()
+                .and_then(|old| previous_snapshots.remove(&old));
 
             previous_snapshots
                 .into_iter()
@@ -203,8 +337,24 @@ impl ElementWithChildrenSnapshot {
                     already_performed
                         .into_iter()
                         .map(|(applied, m)| match applied {
-                            Some(applied) => Ok(applied),
-                            None => perform(m, root_element.clone()),
+                            Some(applied) => controlled::resync(
+                                &applied.mutation,
+                                &root_element,
+                                do_not_move.as_ref(),
+                            )
+                            .map(|()| applied),
+                            None => match is_value(&m).then(|| replaced_value.take()).flatten() {
+                                Some(replaced) => {
+                                    controlled::resync(&m, &root_element, do_not_move.as_ref()).map(
+                                        |()| SnapshotEntryV2 {
+                                            mutation: m,
+                                            // still leads back to the value from before korvin
+                                            log: replaced.log,
+                                        },
+                                    )
+                                }
+                                None => perform(m, root_element.clone()),
+                            },
                         })
                         .collect::<Result<Vec<_>, _>>()
                 })?
@@ -217,6 +367,18 @@ impl ElementWithChildrenSnapshot {
 
             type Children = BTreeMap<ChildKey, MatchingChildren>;
 
+            // where memoized components rendered to on the previous rebuild
+            let mut memoized = self.children.iter().rev().fold(
+                HashMap::<(Option<u64>, TypeId), Vec<TagName>>::new(),
+                |mut acc, child| {
+                    if let Some(memo) = child.memo.as_ref() {
+                        acc.entry((child.element.key, memo.component))
+                            .or_default()
+                            .push(child.element.create.mutation.kind.clone());
+                    }
+                    acc
+                },
+            );
             let mut old_children: Children = {
                 let child_key = |e: &Child| -> ChildKey {
                     (e.element.key, e.element.create.mutation.kind.clone())
@@ -231,29 +393,66 @@ impl ElementWithChildrenSnapshot {
             old_children
                 .values_mut()
                 .for_each(|children| children.reverse());
-            recipe
+            #[cfg(debug_assertions)]
+            duplicate_keys::warn(&root_element, &recipe.children);
+            // every new child is matched first, so that the leftovers are recycled before the
+            // unmatched ones are built and can be reused for them
+            let matched = recipe
                 .children
                 .into_iter()
-                .enumerate()
-                .map(|(index, new)| {
-                    match old_children
+                .map(|new| {
+                    let (new, memo) = match new {
+                        ChildRecipe::Element(new) => (new, None),
+                        ChildRecipe::Memo(MemoRecipe { key, memo, render }) => {
+                            let unchanged = memoized
+                                .get_mut(&(key, memo.component))
+                                .and_then(Vec::pop)
+                                .and_then(|kind| old_children.get_mut(&(key, kind)))
+                                .and_then(|old| {
+                                    old.last()
+                                        .and_then(|old| old.memo.as_ref())
+                                        .is_some_and(|old| old.same(&memo))
+                                        .then(|| old.pop())
+                                        .flatten()
+                                });
+                            if let Some(old) = unchanged {
+                                return Matched::Unchanged(old);
+                            }
+                            (render(), Some(memo))
+                        }
+                    };
+                    let old = old_children
                         .get_mut(&(new.element.key, new.element.create.kind.clone()))
-                        .and_then(|e| e.pop())
-                    {
-                        Some(old) => old.rebuild(root_element.clone(), new, do_not_move.clone()),
-                        None => build_new_child(root_element.clone(), new),
+                        .and_then(|e| e.pop());
+                    Matched::Recipe { old, new, memo }
+                })
+                .collect_vec();
+            old_children
+                .into_values()
+                .flatten()
+                .for_each(|old| pool.recycle(old));
+            matched
+                .into_iter()
+                .map(|matched| match matched {
+                    Matched::Unchanged(old) => {
+                        old.resync_controlled(do_not_move.as_ref()).map(|()| old)
+                    }
+                    Matched::Recipe { old, new, memo } => match old {
+                        Some(old) => old.rebuild(
+                            root_element.clone(),
+                            new,
+                            do_not_move.clone(),
+                            pool,
+                            templates,
+                        ),
+                        None => build_new_child(root_element.clone(), new, pool, templates),
                     }
-                    .map(|child| (index, child))
+                    .map(|child| child.with_memo(memo)),
                 })
                 .collect::<RuntimeResult<Vec<_>>>()
-                .map(|new_children| {
-                    old_children.into_values().flatten().for_each(|e| {
-                        raw_operations::remove_element_in_place(e.element.create.log.element_id);
-                    });
-                    new_children
-                })
-                .map(|children| {
+                .and_then(|children| {
                     reorder_children::SortableChildren {
+                        parent: root_element.clone(),
                         do_not_move,
                         children,
                     }
@@ -275,6 +474,7 @@ impl ElementWithChildrenSnapshot {
                 finish,
             },
             children,
+            memo: None,
         })
     }
 }
@@ -0,0 +1,200 @@
+//! Controlled form values. Set-value mutations are only performed when the recipe changes, so
+//! whatever the user typed since stays in the DOM even when the model rejected or normalised
+//! it. After every rebuild the live value is compared with the recipe and re-applied when
+//! they diverge.
+
+use crate::{
+    data::ElementId,
+    mutation::{
+        element::builder_mutation::modify::ElementBuilderModifyMutation, error::MutationError,
+    },
+    raw_operations, RuntimeError, RuntimeResult,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{CompositionEvent, HtmlInputElement, HtmlTextAreaElement};
+
+/// A value a runtime skipped while its element was composing.
+type Deferred = Option<(ElementBuilderModifyMutation, ElementId)>;
+
+/// Tracks whether an IME composition is in progress anywhere in the document. Replacing the
+/// value mid-composition would commit or drop the half-typed characters, so a rebuild leaves
+/// the composing element alone and its value is resynced once the composition ends.
+struct Composition {
+    composing: Rc<Cell<bool>>,
+    deferred: Rc<RefCell<Deferred>>,
+    _listeners: Vec<Closure<dyn FnMut(CompositionEvent)>>,
+}
+
+impl Composition {
+    fn install() -> Self {
+        let composing = Rc::new(Cell::new(false));
+        let deferred = Rc::new(RefCell::new(None));
+        let listeners = [("compositionstart", true), ("compositionend", false)]
+            .into_iter()
+            .map(|(name, started)| {
+                let composing = composing.clone();
+                let deferred = deferred.clone();
+                let listener =
+                    Closure::<dyn FnMut(CompositionEvent)>::new(move |event: CompositionEvent| {
+                        composing.set(started);
+                        if !started {
+                            resync_deferred(deferred.take(), &event)
+                        }
+                    });
+                let added = crate::DOCUMENT.with(|document| {
+                    document.add_event_listener_with_callback_and_bool(
+                        name,
+                        listener.as_ref().unchecked_ref(),
+                        true,
+                    )
+                });
+                if let Err(message) = added {
+                    tracing::warn!(?message, name, "tracking IME composition");
+                }
+                listener
+            })
+            .collect();
+        Self {
+            composing,
+            deferred,
+            _listeners: listeners,
+        }
+    }
+}
+
+/// Only the element the composition ended in, other runtimes and elements korvin doesn't
+/// manage never had their value deferred.
+fn resync_deferred(deferred: Deferred, event: &CompositionEvent) {
+    let Some((mutation, element)) = deferred else {
+        return;
+    };
+    let target = event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok());
+    if target.as_ref() != Some(element.as_ref()) {
+        return;
+    }
+    if let Err(message) = resync(&mutation, &element, Some(&element)) {
+        tracing::warn!(?message, "resyncing value after composition");
+    }
+}
+
+thread_local! {
+    static COMPOSITION: Composition = Composition::install();
+}
+
+/// Starts listening for composition events, has to happen before the user starts typing.
+pub(super) fn track_composition() {
+    COMPOSITION.with(|_| ())
+}
+
+fn composing() -> bool {
+    COMPOSITION.with(|composition| composition.composing.get())
+}
+
+/// `None` forgets the value deferred for `element`.
+fn defer(element: &ElementId, mutation: Option<&ElementBuilderModifyMutation>) {
+    COMPOSITION.with(|composition| {
+        let mut deferred = composition.deferred.borrow_mut();
+        match mutation {
+            Some(mutation) => *deferred = Some((mutation.clone(), element.clone())),
+            None if deferred
+                .as_ref()
+                .is_some_and(|(_, deferred)| deferred == element) =>
+            {
+                *deferred = None
+            }
+            None => {}
+        }
+    })
+}
+
+/// Caret of a focused text control, restored after its value is replaced.
+fn selection(element: &ElementId) -> Option<(u32, u32)> {
+    let element = element.as_ref();
+    let range = |start: Result<Option<u32>, _>, end: Result<Option<u32>, _>| {
+        start.ok().flatten().zip(end.ok().flatten())
+    };
+    element
+        .dyn_ref::<HtmlInputElement>()
+        .and_then(|input| range(input.selection_start(), input.selection_end()))
+        .or_else(|| {
+            element
+                .dyn_ref::<HtmlTextAreaElement>()
+                .and_then(|textarea| range(textarea.selection_start(), textarea.selection_end()))
+        })
+}
+
+fn restore_selection(element: &ElementId, (start, end): (u32, u32), value: &str) {
+    let length = u32::try_from(value.encode_utf16().count()).unwrap_or(u32::MAX);
+    let (start, end) = (start.min(length), end.min(length));
+    let element = element.as_ref();
+    let restored = element
+        .dyn_ref::<HtmlInputElement>()
+        .map(|input| input.set_selection_range(start, end))
+        .or_else(|| {
+            element
+                .dyn_ref::<HtmlTextAreaElement>()
+                .map(|textarea| textarea.set_selection_range(start, end))
+        });
+    if let Some(Err(message)) = restored {
+        tracing::debug!(?message, "restoring selection");
+    }
+}
+
+/// Re-applies an already performed value mutation when the live state no longer matches it.
+/// `focused` is the element the user is interacting with, its caret is kept in place and it's
+/// left alone while an IME composition is in progress.
+pub(super) fn resync(
+    mutation: &ElementBuilderModifyMutation,
+    element: &ElementId,
+    focused: Option<&ElementId>,
+) -> RuntimeResult<()> {
+    let is_focused = focused == Some(element);
+    match mutation {
+        ElementBuilderModifyMutation::SetInputValue(set_value) => {
+            let expected = set_value.value.as_ref();
+            match raw_operations::input_value(element.as_ref()) {
+                Some(live) if live != expected => {}
+                _ => {
+                    if is_focused {
+                        defer(element, None);
+                    }
+                    return Ok(());
+                }
+            }
+            if is_focused && composing() {
+                defer(element, Some(mutation));
+                return Ok(());
+            }
+            let selection = is_focused.then(|| selection(element)).flatten();
+            raw_operations::set_input_value(element.clone(), set_value.value.clone())
+                .map_err(MutationError::SetInputValue)
+                .map_err(RuntimeError::Mutation)?;
+            if let Some(selection) = selection {
+                restore_selection(element, selection, expected);
+            }
+            Ok(())
+        }
+        ElementBuilderModifyMutation::SetChecked(mutation) => {
+            let live = element
+                .as_ref()
+                .dyn_ref::<HtmlInputElement>()
+                .map(|input| input.checked());
+            match live {
+                Some(live) if live != mutation.checked => {
+                    raw_operations::set_checked(element.clone(), mutation.checked)
+                        .map(|_| ())
+                        .map_err(MutationError::SetChecked)
+                        .map_err(RuntimeError::Mutation)
+                }
+                _ => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
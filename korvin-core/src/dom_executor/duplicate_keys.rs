@@ -0,0 +1,57 @@
+//! Siblings are matched with the previous rebuild by key and tag, duplicates silently fall back
+//! to matching in order. Debug builds point them out.
+
+use crate::{
+    data::ElementId,
+    element_builder::{duplicate_keys, ChildRecipe, DuplicateKey},
+};
+use itertools::Itertools;
+use std::cell::RefCell;
+
+thread_local! {
+    /// New subtrees are built detached, their paths are only known once the rebuild inserted
+    /// them.
+    static PENDING: RefCell<Vec<(ElementId, Vec<DuplicateKey>)>> = const { RefCell::new(vec![]) };
+}
+
+/// Queues a warning for the children of `parent`, see [`flush`].
+pub(super) fn warn(parent: &ElementId, children: &[ChildRecipe]) {
+    let duplicates = duplicate_keys(children);
+    if duplicates.is_empty() {
+        return;
+    }
+    PENDING.with(|pending| pending.borrow_mut().push((parent.clone(), duplicates)));
+}
+
+/// Logs the queued warnings, called when the rebuild is over.
+pub(super) fn flush() {
+    PENDING
+        .with(|pending| pending.take())
+        .into_iter()
+        .for_each(|(parent, duplicates)| {
+            let path = path(parent.as_ref());
+            duplicates.into_iter().for_each(|duplicate| {
+                tracing::warn!(
+                    %path,
+                    ?duplicate,
+                    "siblings share a key, they will be matched in order on the next rebuild"
+                );
+            });
+        });
+}
+
+/// `body > main > ul#todos`, from the outermost ancestor down to `element`.
+fn path(element: &web_sys::Element) -> String {
+    std::iter::successors(Some(element.clone()), |element| element.parent_element())
+        .map(|element| {
+            let tag = element.tag_name().to_lowercase();
+            match element.id() {
+                id if id.is_empty() => tag,
+                id => format!("{tag}#{id}"),
+            }
+        })
+        .collect_vec()
+        .into_iter()
+        .rev()
+        .join(" > ")
+}
@@ -0,0 +1,108 @@
+//! Removed elements can be kept per tag and handed out again instead of creating new ones.
+//! Before an element is pooled its applied mutations are reverted (newest first), which leaves
+//! it as bare as a freshly created one: no attributes, classes, styles, text or listeners.
+//! Elements something else modified, and those with state outside their attributes (form
+//! controls, media), are dropped instead.
+
+use super::ElementWithChildrenSnapshot;
+use crate::{
+    data::{ElementId, TagName},
+    mutation::traits::{Perform, Revert},
+    raw_operations,
+};
+use std::collections::HashMap;
+
+/// Their state (typed values, playback, drawn pixels...) can't be reset from the logs.
+const STATEFUL: &[&str] = &[
+    "input", "textarea", "select", "option", "iframe", "canvas", "video", "audio",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// elements waiting in the pool
+    pub pooled: usize,
+    /// elements handed out again instead of being created
+    pub reused: usize,
+}
+
+/// Disabled (holds nothing) unless given a capacity, see
+/// [`crate::Runtime::with_element_pool`].
+#[derive(Debug, Clone, Default)]
+pub struct ElementPool {
+    free: HashMap<TagName, Vec<ElementId>>,
+    /// elements kept for every tag
+    capacity: usize,
+    pooled: usize,
+    reused: usize,
+}
+
+impl ElementPool {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ..Default::default()
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        PoolStats {
+            pooled: self.pooled,
+            reused: self.reused,
+        }
+    }
+
+    /// A bare element of the given tag, detached from the document.
+    pub(crate) fn take(&mut self, kind: &TagName) -> Option<ElementId> {
+        let element = self.free.get_mut(kind).and_then(Vec::pop)?;
+        self.pooled -= 1;
+        self.reused += 1;
+        Some(element)
+    }
+
+    /// Removes the subtree from the document, pooling whatever fits.
+    pub(crate) fn recycle(&mut self, snapshot: ElementWithChildrenSnapshot) {
+        if self.capacity == 0 {
+            raw_operations::remove_element_in_place(snapshot.element.create.log.element_id);
+            return;
+        }
+        let ElementWithChildrenSnapshot {
+            element, children, ..
+        } = snapshot;
+        let element_id = element.create.log.element_id;
+        raw_operations::remove_element_in_place(element_id.clone());
+        children.into_iter().for_each(|child| self.recycle(child));
+
+        let kind = element.create.log.kind;
+        let free = self.free.entry(kind.clone()).or_default();
+        if free.len() >= self.capacity || STATEFUL.contains(&kind.as_ref()) {
+            return;
+        }
+        let reverted =
+            element.modify.into_iter().rev().try_for_each(|applied| {
+                applied.log.revert().perform(element_id.clone()).map(|_| ())
+            });
+        let raw: &web_sys::Element = element_id.as_ref();
+        // removing the last class or style property leaves the attribute behind, empty
+        ["class", "style"]
+            .into_iter()
+            .filter(|name| {
+                raw.get_attribute(name)
+                    .is_some_and(|value| value.is_empty())
+            })
+            .for_each(|name| {
+                let _ = raw.remove_attribute(name);
+            });
+        match reverted {
+            Ok(()) if !raw.has_attributes() && !raw.has_child_nodes() => {
+                free.push(element_id);
+                self.pooled += 1;
+            }
+            Ok(()) => tracing::trace!(?element_id, "modified outside the runtime, not pooled"),
+            Err(error) => tracing::debug!(?element_id, ?error, "couldn't reset, not pooled"),
+        }
+    }
+}
@@ -1,59 +1,83 @@
 use tracing::instrument;
+use web_sys::Element;
 
-use crate::{data::ElementId, raw_operations};
+use crate::{data::ElementId, raw_operations, RuntimeError, RuntimeResult};
 
 use super::ElementWithChildrenSnapshot;
 
+/// Rebuilt children in their new order. Reused ones are still where they were in the DOM, new
+/// ones are detached subtrees, every child that isn't in place is moved (or inserted) once.
 pub struct SortableChildren {
+    pub parent: ElementId,
     pub do_not_move: Option<ElementId>,
-    pub children: Vec<(usize, ElementWithChildrenSnapshot)>,
+    pub children: Vec<ElementWithChildrenSnapshot>,
 }
 
 impl SortableChildren {
-    fn swap(&mut self, one: usize, other: usize) {
-        raw_operations::swap_siblings(
-            self.children[one].1.element.create.log.element_id.clone(),
-            self.children[other].1.element.create.log.element_id.clone(),
-        )
-        .expect("swapping failed");
-        self.children.swap(one, other)
-    }
     #[instrument(skip(self), level = "trace")]
-    pub fn ordered(mut self) -> Vec<ElementWithChildrenSnapshot> {
-        self.reorder_children();
-        self.children.into_iter().map(|(_, child)| child).collect()
-    }
-    /// yes, this is bubble sort
-    fn reorder_children(&mut self) {
-        let len = self.children.len();
-
-        for i in (0..len).rev() {
-            let mut has_swapped = false;
-            for j in 0..i {
-                if self.children[j].0 > self.children[j + 1].0 {
-                    match self
-                        .do_not_move
-                        .as_ref()
-                        .map(|do_not_move| {
-                            self.children[j]
-                                .1
-                                .element
-                                .create
-                                .log
-                                .element_id
-                                .eq(do_not_move)
-                        })
-                        .unwrap_or_default()
-                    {
-                        false => self.swap(j, j + 1),
-                        true => self.swap(j + 1, j),
-                    };
-                    has_swapped = true;
+    pub fn ordered(self) -> RuntimeResult<Vec<ElementWithChildrenSnapshot>> {
+        let Self {
+            parent,
+            do_not_move,
+            children,
+        } = self;
+        // children before it are in place, so whatever follows it has to be the next child
+        let mut previous: Option<Element> = None;
+        children.iter().try_for_each(|child| {
+            let element_id = &child.element.create.log.element_id;
+            let element: &Element = element_id.as_ref();
+            let next = match previous.as_ref() {
+                Some(previous) => previous.next_element_sibling(),
+                None => parent.as_ref().first_element_child(),
+            };
+            if next.as_ref() != Some(element) {
+                let focused = do_not_move.as_ref().is_some_and(|focused| {
+                    let focused: &Element = focused.as_ref();
+                    element.contains(Some(focused))
+                });
+                match focused && next.is_some() {
+                    // moving the focused element (or its ancestor) would blur it, the ones in
+                    // front of it move behind it instead
+                    true => move_behind(&parent, next, element_id)?,
+                    false => {
+                        let error = match element.parent_element() {
+                            Some(_) => RuntimeError::ReinsertingOldChild,
+                            None => RuntimeError::InsertingNewChild,
+                        };
+                        raw_operations::insert_element_before(
+                            element_id.clone(),
+                            parent.clone(),
+                            next,
+                        )
+                        .map_err(error)?;
+                    }
                 }
             }
-            if !has_swapped {
-                break;
-            }
-        }
+            previous = Some(element.clone());
+            Ok(())
+        })?;
+        Ok(children)
     }
 }
+
+/// Moves the siblings from `from` up to `element` right behind it, keeping their order.
+fn move_behind(
+    parent: &ElementId,
+    from: Option<Element>,
+    element: &ElementId,
+) -> RuntimeResult<()> {
+    let element: &Element = element.as_ref();
+    let in_front = std::iter::successors(from, Element::next_element_sibling)
+        .take_while(|sibling| sibling != element)
+        .collect::<Vec<_>>();
+    let behind = element.next_element_sibling();
+    in_front.into_iter().try_for_each(|sibling| {
+        raw_operations::insert_element_before(
+            ElementId::new(sibling),
+            parent.clone(),
+            behind.clone(),
+        )
+        .map(|_| ())
+        .map_err(RuntimeError::ReinsertingOldChild)
+    })
+}
@@ -0,0 +1,352 @@
+//! Subtrees marked with [`crate::element_builder::AsElementBuilder::template`] are compiled
+//! into a `<template>` the first time a subtree of their shape (tags and child counts) is
+//! built. It holds what HTML can express: attributes, classes, inline styles and text. Every
+//! subtree of that shape is then a deep copy of the template, patched where its recipe differs
+//! from it, with listeners, properties and controlled values performed as usual.
+//!
+//! Templates are kept per runtime, the least recently used one is dropped once there are more
+//! than [`CAPACITY`] shapes.
+
+use super::{perform, ElementSnapshot, ElementWithChildrenSnapshot, SnapshotEntryV2};
+use crate::{
+    component::{Memo, MemoRecipe},
+    data::{AttributeValue, ElementId, TagName},
+    element_builder::{calculate_hash, ChildRecipe, ElementRecipe, ElementWithChildrenRecipe},
+    mutation::{
+        element::builder_mutation::{
+            marker::create::ElementCreateMutationLog,
+            modify::{
+                add_class::ElementAddClassMutationLog,
+                set_attribute::ElementSetAttributeMutationLog,
+                set_style::ElementSetStyleMutationLog, set_text::ElementSetTextMutationLog,
+                ElementBuilderModifyMutation, ElementBuilderModifyMutationLog,
+            },
+        },
+        traits::{Perform, Revert},
+    },
+    raw_operations::{self, error::JsError, error::RawOperationError},
+    RuntimeError, RuntimeResult,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+use wasm_bindgen::JsValue;
+use web_sys::{Element, HtmlTemplateElement};
+
+/// Shapes a runtime keeps templates for.
+pub const CAPACITY: usize = 256;
+
+/// Tag and child count of every node in document order.
+type Shape = Vec<(TagName, usize)>;
+
+#[derive(Debug)]
+struct Template {
+    shape: Shape,
+    template: HtmlTemplateElement,
+    /// Mutations already applied by the template, for every node in document order.
+    baked: Vec<HashSet<ElementBuilderModifyMutation>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TemplateStats {
+    /// templates compiled from a recipe
+    pub compiled: usize,
+    /// subtrees built by cloning a template
+    pub cloned: usize,
+}
+
+/// Compiled templates by the hash of their shape, with the number of clones made when they were
+/// last used.
+#[derive(Debug, Clone, Default)]
+pub struct Templates {
+    compiled: HashMap<u64, (Rc<Template>, usize)>,
+    stats: TemplateStats,
+}
+
+impl Templates {
+    pub fn stats(&self) -> TemplateStats {
+        self.stats
+    }
+
+    fn get_or_compile(&mut self, nodes: &[Node]) -> RuntimeResult<Rc<Template>> {
+        let shape = shape(nodes);
+        let hash = calculate_hash(&shape);
+        let used = self.stats.cloned;
+        // a colliding shape replaces the template instead of being cloned from it
+        if let Some((template, last_used)) = self
+            .compiled
+            .get_mut(&hash)
+            .filter(|(template, _)| template.shape == shape)
+        {
+            *last_used = used;
+            return Ok(template.clone());
+        }
+        let template = Rc::new(compile(nodes, shape)?);
+        self.stats.compiled += 1;
+        self.compiled.insert(hash, (template.clone(), used));
+        if self.compiled.len() > CAPACITY {
+            let least_recent = self
+                .compiled
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(hash, _)| *hash);
+            least_recent.map(|hash| self.compiled.remove(&hash));
+        }
+        Ok(template)
+    }
+}
+
+/// A node of the recipe in document order, with memoized children rendered.
+struct Node {
+    element: ElementRecipe,
+    memo: Option<Memo>,
+    children: usize,
+}
+
+fn flatten(recipe: ElementWithChildrenRecipe, memo: Option<Memo>, nodes: &mut Vec<Node>) {
+    let ElementWithChildrenRecipe {
+        element,
+        children,
+        template: _,
+    } = recipe;
+    nodes.push(Node {
+        element,
+        memo,
+        children: children.len(),
+    });
+    children.into_iter().for_each(|child| match child {
+        ChildRecipe::Element(child) => flatten(child, None, nodes),
+        ChildRecipe::Memo(MemoRecipe { memo, render, .. }) => flatten(render(), Some(memo), nodes),
+    });
+}
+
+fn shape(nodes: &[Node]) -> Shape {
+    nodes
+        .iter()
+        .map(|node| (node.element.create.kind.clone(), node.children))
+        .collect()
+}
+
+/// The log a mutation HTML can express would have on a freshly created element, `None` for the
+/// ones the template can't hold.
+fn baked_log(mutation: &ElementBuilderModifyMutation) -> Option<ElementBuilderModifyMutationLog> {
+    match mutation {
+        ElementBuilderModifyMutation::SetAttribute(mutation) => Some(
+            ElementSetAttributeMutationLog {
+                attribute: mutation.attribute.clone(),
+                previous_value: None,
+            }
+            .into(),
+        ),
+        ElementBuilderModifyMutation::AddClass(mutation) => Some(
+            ElementAddClassMutationLog {
+                class: mutation.class.clone(),
+                was_present: false,
+            }
+            .into(),
+        ),
+        ElementBuilderModifyMutation::SetStyle(mutation) => Some(
+            ElementSetStyleMutationLog {
+                property: mutation.property.clone(),
+                previous_value: None,
+            }
+            .into(),
+        ),
+        ElementBuilderModifyMutation::SetText(_) => Some(
+            ElementSetTextMutationLog {
+                previous_value: Some(AttributeValue::from("")),
+            }
+            .into(),
+        ),
+        _ => None,
+    }
+}
+
+/// Index of every node's parent, nodes being in document order.
+fn parents(nodes: &[Node]) -> Vec<Option<usize>> {
+    // open nodes with the number of children they are still missing
+    let mut open: Vec<(usize, usize)> = vec![];
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| {
+            while open.last().is_some_and(|(_, missing)| *missing == 0) {
+                open.pop();
+            }
+            let parent = open.last_mut().map(|(parent, missing)| {
+                *missing -= 1;
+                *parent
+            });
+            open.push((index, node.children));
+            parent
+        })
+        .collect()
+}
+
+fn compile(nodes: &[Node], shape: Shape) -> RuntimeResult<Template> {
+    let (template, document) = crate::DOCUMENT
+        .with(raw_operations::create_template)
+        .map_err(RuntimeError::Template)?;
+    let elements = nodes
+        .iter()
+        .map(|node| {
+            raw_operations::create_element(&document, node.element.create.kind.clone())
+                .map_err(RuntimeError::Template)
+        })
+        .collect::<RuntimeResult<Vec<_>>>()?;
+    let baked = nodes
+        .iter()
+        .zip(&elements)
+        .map(|(node, element)| {
+            node.element
+                .modify
+                .iter()
+                .filter(|mutation| baked_log(mutation).is_some())
+                .map(|mutation| {
+                    mutation
+                        .clone()
+                        .perform(element.clone())
+                        .map(|_| mutation.clone())
+                        .map_err(RuntimeError::Mutation)
+                })
+                .collect::<RuntimeResult<HashSet<_>>>()
+        })
+        .collect::<RuntimeResult<Vec<_>>>()?;
+    parents(nodes)
+        .into_iter()
+        .zip(&elements)
+        .filter_map(|(parent, element)| parent.map(|parent| (&elements[parent], element)))
+        .try_for_each(|(parent, element)| {
+            raw_operations::insert_element(element.clone(), parent.clone())
+                .map(|_| ())
+                .map_err(RuntimeError::Template)
+        })?;
+    raw_operations::fill_template(&template, elements[0].clone())
+        .map_err(RuntimeError::Template)?;
+    Ok(Template {
+        shape,
+        template,
+        baked,
+    })
+}
+
+/// `root` and its descendants in document order.
+fn descendants(root: Element, into: &mut Vec<Element>) {
+    let children = std::iter::successors(root.first_element_child(), Element::next_element_sibling);
+    into.push(root);
+    children.for_each(|child| descendants(child, into));
+}
+
+fn assemble(
+    entries: &mut impl Iterator<Item = (ElementSnapshot, Option<Memo>, usize)>,
+) -> Option<ElementWithChildrenSnapshot> {
+    let (element, memo, children) = entries.next()?;
+    let children = (0..children)
+        .map(|_| assemble(entries))
+        .collect::<Option<Vec<_>>>()?;
+    Some(ElementWithChildrenSnapshot {
+        element,
+        children,
+        memo,
+    })
+}
+
+fn mismatch(message: &str) -> RuntimeError {
+    RuntimeError::Template(RawOperationError::CloningTemplate(JsError::from(
+        JsValue::from_str(message),
+    )))
+}
+
+/// Builds the subtree detached, like [`super::build_new_child`].
+pub(super) fn build(
+    recipe: ElementWithChildrenRecipe,
+    templates: &mut Templates,
+) -> RuntimeResult<ElementWithChildrenSnapshot> {
+    let mut nodes = vec![];
+    flatten(recipe, None, &mut nodes);
+    // the clone's children are already attached, patching the text would replace them
+    if let Some(node) = nodes.iter().find(|node| {
+        node.children > 0
+            && node
+                .element
+                .modify
+                .iter()
+                .any(|mutation| matches!(mutation, ElementBuilderModifyMutation::SetText(_)))
+    }) {
+        return Err(RuntimeError::TemplateTextWithChildren {
+            tag: node.element.create.kind.as_ref().to_owned(),
+        });
+    }
+    let template = templates.get_or_compile(&nodes)?;
+    let root = crate::DOCUMENT
+        .with(|document| raw_operations::clone_template(document, &template.template))
+        .map_err(RuntimeError::Template)?;
+    templates.stats.cloned += 1;
+    let mut elements = vec![];
+    descendants(root, &mut elements);
+    if elements.len() != nodes.len() || template.baked.len() != nodes.len() {
+        return Err(mismatch("template doesn't match the recipe's shape"));
+    }
+
+    let mut entries = nodes
+        .into_iter()
+        .zip(elements)
+        .zip(&template.baked)
+        .map(|((node, element), baked)| {
+            let Node {
+                element:
+                    ElementRecipe {
+                        key,
+                        create,
+                        modify,
+                        finish,
+                    },
+                memo,
+                children,
+            } = node;
+            let element_id = ElementId::created(element);
+            // leftovers of the template go first, so that what replaces them is logged as if
+            // performed on a fresh element
+            baked
+                .iter()
+                .filter(|baked| !modify.contains(baked))
+                .filter_map(baked_log)
+                .try_for_each(|log| {
+                    log.revert()
+                        .perform(element_id.clone())
+                        .map(|_| ())
+                        .map_err(RuntimeError::UndoingTrailingMutations)
+                })?;
+            let modify = modify
+                .into_iter()
+                .map(|mutation| match baked.contains(&mutation) {
+                    true => baked_log(&mutation)
+                        .map(|log| SnapshotEntryV2 { mutation, log })
+                        .ok_or_else(|| mismatch("only mutations HTML can express are baked")),
+                    false => perform(mutation, element_id.clone()),
+                })
+                .collect::<RuntimeResult<Vec<_>>>()?;
+            let finish = perform(finish, element_id.clone())?;
+            let create = SnapshotEntryV2 {
+                log: ElementCreateMutationLog {
+                    kind: create.kind.clone(),
+                    element_id,
+                },
+                mutation: create,
+            };
+            Ok((
+                ElementSnapshot {
+                    key,
+                    create,
+                    modify,
+                    finish,
+                },
+                memo,
+                children,
+            ))
+        })
+        .collect::<RuntimeResult<Vec<_>>>()?
+        .into_iter();
+    assemble(&mut entries).ok_or_else(|| mismatch("template doesn't match the recipe's shape"))
+}
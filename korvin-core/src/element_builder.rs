@@ -1,83 +1,69 @@
-use self::value_cache::IntoJsValue;
+use self::value_cache::{IntoJsValue, IntoOptionalJsValue};
 use crate::{
-    data::{AttributeName, AttributeValue, EventListenerWrapper, KorvinClosure, TagName},
+    component::{Deferred, MemoRecipe},
+    data::{
+        AttributeName, AttributeValue, EventListenerWrapper, EventTasks, KorvinClosure,
+        PropertyName, PropertyValue, TagName,
+    },
     mutation::{
         element::builder_mutation::{
             marker::create::ElementCreateMutation,
             marker::finish::ElementFinishMutation,
+            modify::add_class::ElementAddClassMutation,
             modify::set_text::ElementSetTextMutation,
             modify::{
                 add_event_listener::{by_event_kind, ElementAddEventListenerMutation},
                 ElementBuilderModifyMutation,
             },
             modify::{
-                set_attribute::ElementSetAttributeMutation,
+                set_attribute::ElementSetAttributeMutation, set_checked::ElementSetCheckedMutation,
                 set_input_value::ElementSetInputValueMutation,
+                set_property::ElementSetPropertyMutation, set_style::ElementSetStyleMutation,
+                use_stylesheet::ElementUseStylesheetMutation,
             },
         },
         traits::Perform,
     },
+    raw_operations::SELECTED_VALUES_SEPARATOR,
+    style::Stylesheet,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    future::Future,
+    hash::Hasher,
+    iter::empty,
+    rc::Rc,
 };
-use std::{collections::BTreeMap, hash::Hasher, iter::empty, sync::Arc};
 use wasm_bindgen::{
     convert::{FromWasmAbi, RefFromWasmAbi},
     prelude::Closure,
 };
+use web_sys::AbortSignal;
 
-pub mod value_cache {
-    use super::calculate_hash;
-    use crate::data::Value;
-    use std::{cell::RefCell, collections::BTreeMap};
-
-    pub trait IntoJsValue: std::hash::Hash + Copy {
-        fn into_value(self) -> Value;
-    }
-
-    impl<T> IntoJsValue for T
-    where
-        T: Into<Value> + std::hash::Hash + Copy,
-    {
-        fn into_value(self) -> Value {
-            self.into()
-        }
-    }
-
-    #[derive(Default)]
-    pub(crate) struct ValueCache {
-        previous: BTreeMap<u64, Value>,
-        current: BTreeMap<u64, Value>,
-    }
-
-    impl ValueCache {
-        pub(super) fn cached(&mut self, value: impl IntoJsValue) -> Value {
-            let hash = calculate_hash(&value);
-            self.current
-                .entry(hash)
-                .or_insert_with(|| {
-                    self.previous
-                        .remove(&hash)
-                        .unwrap_or_else(|| value.into_value())
-                })
-                .clone()
-        }
-        pub(crate) fn next_rebuild(&mut self) {
-            std::mem::swap(&mut self.current, &mut self.previous);
-            self.current.clear();
-        }
-    }
-
-    thread_local! {
-        pub(crate) static VALUE_CACHE: RefCell<ValueCache> = Default::default();
-    }
-}
+pub mod value_cache;
 
 pub trait AsElementBuilder {
     fn into_builder(self) -> ElementBuilder;
     fn builder(kind: impl IntoJsValue) -> ElementBuilder {
         ElementBuilder::builder(kind)
     }
-    fn text(self, text: impl IntoJsValue) -> ElementBuilder;
-    fn input_value(self, value: impl IntoJsValue) -> ElementBuilder;
+    fn text(self, text: impl IntoOptionalJsValue) -> ElementBuilder;
+    /// Controls the value of `<input>`, `<textarea>` and `<select>` elements, or the text of a
+    /// contenteditable element.
+    fn input_value(self, value: impl IntoOptionalJsValue) -> ElementBuilder;
+    /// Controls the selected options of a `<select multiple>`.
+    fn selected_values<'a>(self, values: impl IntoIterator<Item = &'a str>) -> ElementBuilder
+    where
+        Self: Sized,
+    {
+        let values = values
+            .into_iter()
+            .collect::<Vec<_>>()
+            .join(&SELECTED_VALUES_SEPARATOR.to_string());
+        self.input_value(values)
+    }
+    /// Controls `.checked` of a checkbox or radio `<input>`.
+    fn checked(self, checked: bool) -> ElementBuilder;
     fn event<Key: std::hash::Hash, EventKind>(
         self,
         key: Key,
@@ -88,13 +74,82 @@ pub trait AsElementBuilder {
         EventKind: std::fmt::Debug + Sized + RefFromWasmAbi + FromWasmAbi + 'static,
         by_event_kind::ElementAddEventListenerMutation<EventKind>:
             Into<ElementAddEventListenerMutation> + Perform;
+    /// Like [`AsElementBuilder::event`], but the callback returns a future which gets spawned
+    /// on every event. Tasks still running when the element is removed (or the listener is
+    /// replaced by one with a different key) are aborted: the [`AbortSignal`] passed to the
+    /// callback fires and the future is dropped.
+    fn event_async<Key: std::hash::Hash, EventKind, Fut>(
+        self,
+        key: Key,
+        name: impl IntoJsValue,
+        callback: impl Fn(EventKind, AbortSignal) -> Fut + 'static,
+    ) -> ElementBuilder
+    where
+        Fut: Future<Output = ()> + 'static,
+        EventKind: std::fmt::Debug + Sized + RefFromWasmAbi + FromWasmAbi + 'static,
+        by_event_kind::ElementAddEventListenerMutation<EventKind>:
+            Into<ElementAddEventListenerMutation> + Perform;
     fn child(self, child: impl Into<ElementBuilder>) -> ElementBuilder;
     fn key(self, key: impl std::hash::Hash) -> ElementBuilder;
     fn children(
         self,
         children: impl IntoIterator<Item = impl Into<ElementBuilder>>,
     ) -> ElementBuilder;
-    fn attribute(self, attribute: impl IntoJsValue, value: impl IntoJsValue) -> ElementBuilder;
+    /// Renders a child for every item, keyed by `key`, so that reordering the items moves
+    /// their elements instead of rebuilding them. Keys have to be unique among the siblings,
+    /// debug builds warn about duplicates.
+    fn keyed_children<Item, Key, Child>(
+        self,
+        items: impl IntoIterator<Item = Item>,
+        key: impl Fn(&Item) -> Key,
+        render: impl Fn(Item) -> Child,
+    ) -> ElementBuilder
+    where
+        Self: Sized,
+        Key: std::hash::Hash,
+        Child: Into<ElementBuilder>,
+    {
+        self.children(items.into_iter().map(|item| {
+            let key = key(&item);
+            render(item).into().key(key)
+        }))
+    }
+    fn attribute(
+        self,
+        attribute: impl IntoJsValue,
+        value: impl IntoOptionalJsValue,
+    ) -> ElementBuilder;
+    /// Sets a JS property (`indeterminate`, `muted`, `scrollTop`, `srcObject`...) instead of an
+    /// attribute. Removing it from the view restores the value it had before.
+    fn property(
+        self,
+        property: impl IntoJsValue,
+        value: impl Into<PropertyValue>,
+    ) -> ElementBuilder;
+    /// Adds a class through `classList`. Unlike the `class` attribute, classes added by other
+    /// code are left alone, and removing one from the view only removes it if korvin added it.
+    /// Whitespace separated names add every one of them.
+    fn class(self, class: &str) -> ElementBuilder;
+    fn class_if(self, class: &str, enabled: bool) -> ElementBuilder
+    where
+        Self: Sized,
+    {
+        match enabled {
+            true => self.class(class),
+            false => self.into_builder(),
+        }
+    }
+    /// Sets a single inline style property, restoring its previous value once removed from the
+    /// view.
+    fn style(self, property: impl IntoJsValue, value: impl IntoOptionalJsValue) -> ElementBuilder;
+    /// Adds the stylesheet's scope class, the stylesheet stays injected while any built element
+    /// uses it.
+    fn stylesheet(self, stylesheet: &Stylesheet) -> ElementBuilder;
+    /// Marks the subtree as mostly static. The first one built is compiled into a `<template>`,
+    /// later subtrees of the same shape are cloned from it and only their differences (and
+    /// what HTML can't express: listeners, properties, controlled values) are applied. Elements
+    /// in it can't have both text and children, wrap the text in an element of its own.
+    fn template(self) -> ElementBuilder;
     fn build(self) -> ElementWithChildrenRecipe;
 }
 
@@ -102,10 +157,18 @@ pub struct ElementBuilder {
     key: Option<u64>,
     kind: TagName,
     attributes: BTreeMap<AttributeName, AttributeValue>,
+    properties: BTreeMap<PropertyName, PropertyValue>,
+    classes: BTreeSet<AttributeValue>,
+    styles: BTreeMap<AttributeName, AttributeValue>,
+    stylesheets: BTreeSet<ElementUseStylesheetMutation>,
     text: Option<AttributeValue>,
     input_value: Option<AttributeValue>,
+    checked: Option<bool>,
     children: Vec<ElementBuilder>,
     event_listeners: Vec<ElementAddEventListenerMutation>,
+    /// Set for [`crate::component::Component::memo`] placeholders, rendered once the executor needs them.
+    deferred: Option<Deferred>,
+    template: bool,
 }
 
 pub fn calculate_hash<T: std::hash::Hash>(t: &T) -> u64 {
@@ -125,19 +188,83 @@ pub struct ElementRecipe {
 #[derive(Debug)]
 pub struct ElementWithChildrenRecipe {
     pub element: ElementRecipe,
-    pub children: Vec<Self>,
+    pub children: Vec<ChildRecipe>,
+    /// Built by cloning a `<template>`, see [`AsElementBuilder::template`].
+    pub template: bool,
+}
+
+#[derive(Debug)]
+pub enum ChildRecipe {
+    Element(ElementWithChildrenRecipe),
+    Memo(MemoRecipe),
 }
 
-macro_rules! cached {
-    ($value:expr) => {
-        value_cache::VALUE_CACHE.with(|value_cache| {
-            value_cache
-                .try_borrow_mut()
-                .ok()
-                .map(|mut value_cache| value_cache.cached($value))
-                .unwrap_or_else(|| $value.into_value())
+/// Keyed siblings of the same kind, which the executor can't tell apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateKey {
+    pub key: u64,
+    /// Tag of the elements, or type name of the memoized component.
+    pub kind: String,
+    /// Indices among the siblings.
+    pub positions: Vec<usize>,
+}
+
+impl ElementWithChildrenRecipe {
+    /// Children sharing a key. On the next rebuild they are matched with the previous ones in
+    /// order, which can hand one item's DOM state (focus, input value, memoized render) to
+    /// another.
+    pub fn duplicate_keys(&self) -> Vec<DuplicateKey> {
+        duplicate_keys(&self.children)
+    }
+}
+
+pub(crate) fn duplicate_keys(children: &[ChildRecipe]) -> Vec<DuplicateKey> {
+    let mut duplicates = children
+        .iter()
+        .enumerate()
+        .filter_map(|(position, child)| {
+            let (key, kind) = match child {
+                ChildRecipe::Element(child) => (
+                    child.element.key?,
+                    child.element.create.kind.as_ref().to_owned(),
+                ),
+                ChildRecipe::Memo(child) => (child.key?, child.memo.name().to_owned()),
+            };
+            Some(((key, kind), position))
+        })
+        .fold(
+            BTreeMap::<(u64, String), Vec<usize>>::new(),
+            |mut acc, (key, position)| {
+                acc.entry(key).or_default().push(position);
+                acc
+            },
+        )
+        .into_iter()
+        .filter(|(_, positions)| positions.len() > 1)
+        .map(|((key, kind), positions)| DuplicateKey {
+            key,
+            kind,
+            positions,
         })
-    };
+        .collect::<Vec<_>>();
+    duplicates.sort_by_key(|duplicate| duplicate.positions[0]);
+    duplicates
+}
+
+impl ElementBuilder {
+    pub(crate) fn deferred(deferred: Deferred) -> Self {
+        Self {
+            deferred: Some(deferred),
+            ..<Self as AsElementBuilder>::builder("")
+        }
+    }
+
+    fn into_child_recipe(mut self) -> ChildRecipe {
+        match self.deferred.take() {
+            Some(deferred) => ChildRecipe::Memo(deferred.into_recipe(self.key)),
+            None => ChildRecipe::Element(self.build()),
+        }
+    }
 }
 
 impl<'a> From<&'a str> for ElementBuilder {
@@ -151,14 +278,18 @@ impl<'a> AsElementBuilder for &'a str {
         ElementBuilder::from(self).into_builder()
     }
 
-    fn text(self, text: impl IntoJsValue) -> ElementBuilder {
+    fn text(self, text: impl IntoOptionalJsValue) -> ElementBuilder {
         ElementBuilder::from(self).text(text)
     }
 
-    fn input_value(self, value: impl IntoJsValue) -> ElementBuilder {
+    fn input_value(self, value: impl IntoOptionalJsValue) -> ElementBuilder {
         ElementBuilder::from(self).input_value(value)
     }
 
+    fn checked(self, checked: bool) -> ElementBuilder {
+        ElementBuilder::from(self).checked(checked)
+    }
+
     fn event<Key: std::hash::Hash, EventKind>(
         self,
         key: Key,
@@ -173,6 +304,21 @@ impl<'a> AsElementBuilder for &'a str {
         ElementBuilder::from(self).event(key, name, callback)
     }
 
+    fn event_async<Key: std::hash::Hash, EventKind, Fut>(
+        self,
+        key: Key,
+        name: impl IntoJsValue,
+        callback: impl Fn(EventKind, AbortSignal) -> Fut + 'static,
+    ) -> ElementBuilder
+    where
+        Fut: Future<Output = ()> + 'static,
+        EventKind: std::fmt::Debug + Sized + RefFromWasmAbi + FromWasmAbi + 'static,
+        by_event_kind::ElementAddEventListenerMutation<EventKind>:
+            Into<ElementAddEventListenerMutation> + Perform,
+    {
+        ElementBuilder::from(self).event_async(key, name, callback)
+    }
+
     fn child(self, child: impl Into<ElementBuilder>) -> ElementBuilder {
         ElementBuilder::from(self).child(child.into())
     }
@@ -188,10 +334,38 @@ impl<'a> AsElementBuilder for &'a str {
         ElementBuilder::from(self).children(children)
     }
 
-    fn attribute(self, attribute: impl IntoJsValue, value: impl IntoJsValue) -> ElementBuilder {
+    fn attribute(
+        self,
+        attribute: impl IntoJsValue,
+        value: impl IntoOptionalJsValue,
+    ) -> ElementBuilder {
         ElementBuilder::from(self).attribute(attribute, value)
     }
 
+    fn property(
+        self,
+        property: impl IntoJsValue,
+        value: impl Into<PropertyValue>,
+    ) -> ElementBuilder {
+        ElementBuilder::from(self).property(property, value)
+    }
+
+    fn class(self, class: &str) -> ElementBuilder {
+        ElementBuilder::from(self).class(class)
+    }
+
+    fn style(self, property: impl IntoJsValue, value: impl IntoOptionalJsValue) -> ElementBuilder {
+        ElementBuilder::from(self).style(property, value)
+    }
+
+    fn stylesheet(self, stylesheet: &Stylesheet) -> ElementBuilder {
+        ElementBuilder::from(self).stylesheet(stylesheet)
+    }
+
+    fn template(self) -> ElementBuilder {
+        ElementBuilder::from(self).template()
+    }
+
     fn build(self) -> ElementWithChildrenRecipe {
         ElementBuilder::from(self).build()
     }
@@ -202,25 +376,41 @@ impl AsElementBuilder for ElementBuilder {
         self
     }
     fn builder(kind: impl IntoJsValue) -> Self {
-        let kind = cached!(kind).into();
+        let kind = value_cache::cached(kind).into();
         Self {
             key: None,
             kind,
             input_value: None,
+            checked: None,
             text: None,
             event_listeners: Default::default(),
             attributes: Default::default(),
+            properties: Default::default(),
+            classes: Default::default(),
+            styles: Default::default(),
+            stylesheets: Default::default(),
             children: Default::default(),
+            deferred: None,
+            template: false,
         }
     }
 
-    fn text(mut self, text: impl IntoJsValue) -> Self {
-        self.text = Some(cached!(text).into());
+    fn text(mut self, text: impl IntoOptionalJsValue) -> Self {
+        self.text = text
+            .into_optional()
+            .map(|text| value_cache::cached(text).into());
+        self
+    }
+
+    fn input_value(mut self, value: impl IntoOptionalJsValue) -> Self {
+        self.input_value = value
+            .into_optional()
+            .map(|value| value_cache::cached(value).into());
         self
     }
 
-    fn input_value(mut self, value: impl IntoJsValue) -> Self {
-        self.input_value = Some(cached!(value).into());
+    fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
         self
     }
     fn event<Key: std::hash::Hash, EventKind>(
@@ -236,16 +426,30 @@ impl AsElementBuilder for ElementBuilder {
     {
         let hash = calculate_hash(&key);
         let listener = EventListenerWrapper::<EventKind> {
-            name: cached!(name).into(),
-            closure: KorvinClosure {
-                hash,
-                closure: Arc::new(Closure::new(callback)),
-            },
+            name: value_cache::cached(name).into(),
+            closure: KorvinClosure::new(hash, Closure::new(callback)),
         };
         self.event_listeners
             .push(by_event_kind::ElementAddEventListenerMutation { listener }.into());
         self
     }
+    fn event_async<Key: std::hash::Hash, EventKind, Fut>(
+        self,
+        key: Key,
+        name: impl IntoJsValue,
+        callback: impl Fn(EventKind, AbortSignal) -> Fut + 'static,
+    ) -> Self
+    where
+        Fut: Future<Output = ()> + 'static,
+        EventKind: std::fmt::Debug + Sized + RefFromWasmAbi + FromWasmAbi + 'static,
+        by_event_kind::ElementAddEventListenerMutation<EventKind>:
+            Into<ElementAddEventListenerMutation> + Perform,
+    {
+        let tasks = Rc::new(EventTasks::default());
+        self.event(key, name, move |event: EventKind| {
+            tasks.spawn(|signal| callback(event, signal))
+        })
+    }
     fn child(mut self, child: impl Into<ElementBuilder>) -> Self {
         self.children.push(child.into());
         self
@@ -261,21 +465,81 @@ impl AsElementBuilder for ElementBuilder {
             .fold(self, |parent, child| parent.child(child))
     }
 
-    fn attribute(mut self, attribute: impl IntoJsValue, value: impl IntoJsValue) -> Self {
-        self.attributes
-            .insert(cached!(attribute).into(), cached!(value).into());
+    fn attribute(mut self, attribute: impl IntoJsValue, value: impl IntoOptionalJsValue) -> Self {
+        let attribute = value_cache::cached(attribute).into();
+        match value.into_optional() {
+            Some(value) => {
+                self.attributes
+                    .insert(attribute, value_cache::cached(value).into());
+            }
+            None => {
+                self.attributes.remove(&attribute);
+            }
+        }
         self
     }
 
-    fn build(self) -> ElementWithChildrenRecipe {
+    fn property(mut self, property: impl IntoJsValue, value: impl Into<PropertyValue>) -> Self {
+        self.properties
+            .insert(value_cache::cached(property).into(), value.into());
+        self
+    }
+
+    fn class(mut self, class: &str) -> Self {
+        self.classes.extend(
+            class
+                .split_whitespace()
+                .map(|class| value_cache::cached(class).into()),
+        );
+        self
+    }
+
+    fn style(mut self, property: impl IntoJsValue, value: impl IntoOptionalJsValue) -> Self {
+        let property = value_cache::cached(property).into();
+        match value.into_optional() {
+            Some(value) => {
+                self.styles
+                    .insert(property, value_cache::cached(value).into());
+            }
+            None => {
+                self.styles.remove(&property);
+            }
+        }
+        self
+    }
+
+    fn stylesheet(mut self, stylesheet: &Stylesheet) -> Self {
+        self.stylesheets.insert(ElementUseStylesheetMutation {
+            scope: value_cache::cached(stylesheet.scope()).into(),
+            css: value_cache::cached(stylesheet.to_css()).into(),
+        });
+        self.class(stylesheet.scope())
+    }
+
+    fn template(mut self) -> Self {
+        self.template = true;
+        self
+    }
+
+    fn build(mut self) -> ElementWithChildrenRecipe {
+        if let Some(deferred) = self.deferred.take() {
+            return (deferred.into_recipe(self.key).render)();
+        }
         let Self {
             key,
             kind,
             attributes,
+            properties,
+            classes,
+            styles,
+            stylesheets,
             children,
             text,
             event_listeners,
             input_value,
+            checked,
+            deferred: _,
+            template,
         } = self;
 
         let element = ElementRecipe {
@@ -291,6 +555,32 @@ impl AsElementBuilder for ElementBuilder {
                         })
                         .map(ElementBuilderModifyMutation::from),
                 )
+                .chain(
+                    stylesheets
+                        .into_iter()
+                        .map(ElementBuilderModifyMutation::from),
+                )
+                .chain(
+                    classes
+                        .into_iter()
+                        .map(|class| ElementAddClassMutation { class })
+                        .map(ElementBuilderModifyMutation::from),
+                )
+                .chain(
+                    styles
+                        .into_iter()
+                        .map(|(property, value)| ElementSetStyleMutation {
+                            property,
+                            value: Some(value),
+                        })
+                        .map(ElementBuilderModifyMutation::from),
+                )
+                .chain(
+                    properties
+                        .into_iter()
+                        .map(|(property, value)| ElementSetPropertyMutation { property, value })
+                        .map(ElementBuilderModifyMutation::from),
+                )
                 .chain(
                     text.into_iter()
                         .map(|value| ElementSetTextMutation { value: Some(value) })
@@ -302,6 +592,12 @@ impl AsElementBuilder for ElementBuilder {
                         .map(|value| ElementSetInputValueMutation { value })
                         .map(ElementBuilderModifyMutation::from),
                 )
+                .chain(
+                    checked
+                        .into_iter()
+                        .map(|checked| ElementSetCheckedMutation { checked })
+                        .map(ElementBuilderModifyMutation::from),
+                )
                 .chain(
                     event_listeners
                         .into_iter()
@@ -312,7 +608,8 @@ impl AsElementBuilder for ElementBuilder {
         };
         ElementWithChildrenRecipe {
             element,
-            children: children.into_iter().map(Self::build).collect(),
+            children: children.into_iter().map(Self::into_child_recipe).collect(),
+            template,
         }
     }
 }
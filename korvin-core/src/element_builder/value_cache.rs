@@ -0,0 +1,304 @@
+//! Values built for the DOM are cached between rebuilds, so that an unchanged value isn't
+//! converted (and allocated) again. Every runtime has its own cache, builders use the one of
+//! the runtime they are built for (see [`crate::Runtime::build`]) and a per-thread
+//! default otherwise. Values are matched by what they were built from, never by hash alone.
+
+use super::calculate_hash;
+use crate::data::Value;
+use std::{borrow::Cow, cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
+
+/// Anything that can be turned into a string for the DOM.
+pub trait IntoJsValue {
+    fn cache_key(&self) -> CacheKey<'_>;
+    fn into_value(self) -> Value;
+}
+
+/// What a value is cached by. Numbers keep their type's representation, `1_u8` and `1.0`
+/// don't share an entry even though both are displayed as `"1"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheKey<'a> {
+    /// Compared with the cached string itself.
+    Str(&'a str),
+    Char(char),
+    Signed(i128),
+    Unsigned(u128),
+    F32(u32),
+    F64(u64),
+}
+
+impl CacheKey<'_> {
+    /// `None` for strings, which are matched with the value they were cached as.
+    fn stored(self) -> Option<CacheKey<'static>> {
+        match self {
+            Self::Str(_) => None,
+            Self::Char(char) => Some(CacheKey::Char(char)),
+            Self::Signed(signed) => Some(CacheKey::Signed(signed)),
+            Self::Unsigned(unsigned) => Some(CacheKey::Unsigned(unsigned)),
+            Self::F32(bits) => Some(CacheKey::F32(bits)),
+            Self::F64(bits) => Some(CacheKey::F64(bits)),
+        }
+    }
+}
+
+macro_rules! strings {
+    ($($string:ty),* $(,)?) => {
+        $(
+            impl IntoJsValue for $string {
+                fn cache_key(&self) -> CacheKey<'_> {
+                    CacheKey::Str(self.as_ref())
+                }
+
+                fn into_value(self) -> Value {
+                    Value::from(self)
+                }
+            }
+        )*
+    };
+}
+
+strings!(&str, String, Cow<'_, str>, Box<str>, Arc<str>);
+
+impl IntoJsValue for &String {
+    fn cache_key(&self) -> CacheKey<'_> {
+        CacheKey::Str(self.as_str())
+    }
+
+    fn into_value(self) -> Value {
+        self.as_str().into_value()
+    }
+}
+
+impl IntoJsValue for char {
+    fn cache_key(&self) -> CacheKey<'_> {
+        CacheKey::Char(*self)
+    }
+
+    fn into_value(self) -> Value {
+        Value::from(self.to_string())
+    }
+}
+
+macro_rules! integers {
+    ($variant:ident as $widened:ty: $($integer:ty),* $(,)?) => {
+        $(
+            impl IntoJsValue for $integer {
+                fn cache_key(&self) -> CacheKey<'_> {
+                    CacheKey::$variant(*self as $widened)
+                }
+
+                fn into_value(self) -> Value {
+                    Value::from(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+integers!(Signed as i128: i8, i16, i32, i64, i128, isize);
+integers!(Unsigned as u128: u8, u16, u32, u64, u128, usize);
+
+macro_rules! floats {
+    ($($float:ty => $variant:ident),* $(,)?) => {
+        $(
+            /// Formatted with `Display`, `1.0` becomes `"1"`.
+            impl IntoJsValue for $float {
+                fn cache_key(&self) -> CacheKey<'_> {
+                    CacheKey::$variant(self.to_bits())
+                }
+
+                fn into_value(self) -> Value {
+                    Value::from(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+floats!(f32 => F32, f64 => F64);
+
+/// Values that can also be absent: `None` leaves the attribute (or text, style...) out,
+/// and so does `false`, which makes `bool` fit boolean attributes like `disabled`.
+pub trait IntoOptionalJsValue {
+    type Value: IntoJsValue;
+    fn into_optional(self) -> Option<Self::Value>;
+}
+
+impl<T: IntoJsValue> IntoOptionalJsValue for T {
+    type Value = T;
+    fn into_optional(self) -> Option<Self::Value> {
+        Some(self)
+    }
+}
+
+impl<T: IntoJsValue> IntoOptionalJsValue for Option<T> {
+    type Value = T;
+    fn into_optional(self) -> Option<Self::Value> {
+        self
+    }
+}
+
+impl IntoOptionalJsValue for bool {
+    type Value = &'static str;
+    fn into_optional(self) -> Option<Self::Value> {
+        self.then_some("")
+    }
+}
+
+/// Values kept when a runtime doesn't set its own capacity.
+pub const DEFAULT_CAPACITY: usize = 4096;
+
+struct Entry {
+    key: Option<CacheKey<'static>>,
+    value: Value,
+    /// Rebuild the entry was last used in.
+    used: u64,
+}
+
+impl Entry {
+    fn matches(&self, key: &CacheKey<'_>) -> bool {
+        match (key, self.key.as_ref()) {
+            (CacheKey::Str(string), None) => self.value.as_ref() == *string,
+            (key, Some(stored)) => key == stored,
+            (_, None) => false,
+        }
+    }
+}
+
+/// Least recently used values are dropped once a rebuild leaves more than `capacity` of them.
+pub struct ValueCache {
+    /// Buckets by hash of the key, colliding keys share a bucket.
+    entries: HashMap<u64, Vec<Entry>>,
+    len: usize,
+    capacity: usize,
+    rebuild: u64,
+}
+
+impl Default for ValueCache {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl std::fmt::Debug for ValueCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValueCache")
+            .field("len", &self.len)
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ValueCache {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Default::default(),
+            len: 0,
+            capacity,
+            rebuild: 0,
+        }
+    }
+
+    pub fn cached(&mut self, value: impl IntoJsValue) -> Value {
+        let key = value.cache_key();
+        let rebuild = self.rebuild;
+        let bucket = self.entries.entry(calculate_hash(&key)).or_default();
+        if let Some(entry) = bucket.iter_mut().find(|entry| entry.matches(&key)) {
+            entry.used = rebuild;
+            return entry.value.clone();
+        }
+        let stored = key.stored();
+        let value = value.into_value();
+        bucket.push(Entry {
+            key: stored,
+            value: value.clone(),
+            used: rebuild,
+        });
+        self.len += 1;
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Evicts the least recently used values down to the capacity.
+    pub fn next_rebuild(&mut self) {
+        self.rebuild += 1;
+        if self.len <= self.capacity {
+            return;
+        }
+        let mut used = self
+            .entries
+            .values()
+            .flatten()
+            .map(|entry| entry.used)
+            .collect::<Vec<_>>();
+        let excess = self.len - self.capacity;
+        let (_, &mut newest_evicted, _) = used.select_nth_unstable(excess - 1);
+        // the newest evicted rebuild's values are only partly evicted
+        let mut evicted_newest =
+            excess - used.iter().filter(|used| **used < newest_evicted).count();
+        self.entries.retain(|_, bucket| {
+            bucket.retain(|entry| match entry.used.cmp(&newest_evicted) {
+                std::cmp::Ordering::Less => false,
+                std::cmp::Ordering::Equal if evicted_newest > 0 => {
+                    evicted_newest -= 1;
+                    false
+                }
+                _ => true,
+            });
+            !bucket.is_empty()
+        });
+        self.len = self.entries.values().map(Vec::len).sum();
+    }
+}
+
+pub(crate) type SharedValueCache = Rc<RefCell<ValueCache>>;
+
+thread_local! {
+    static DEFAULT_CACHE: SharedValueCache = Default::default();
+    static ACTIVE_CACHE: RefCell<Option<SharedValueCache>> = Default::default();
+}
+
+/// Makes `cache` the one builders use while `f` runs.
+pub(crate) fn with_cache<R>(cache: &SharedValueCache, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<SharedValueCache>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            ACTIVE_CACHE.with(|active| *active.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(ACTIVE_CACHE.with(|active| active.replace(Some(cache.clone()))));
+    f()
+}
+
+/// The default cache is shared by every runtime on the thread, each of their rebuilds counts.
+pub(crate) fn next_default_rebuild() {
+    DEFAULT_CACHE.with(|cache| {
+        if let Ok(mut cache) = cache.try_borrow_mut() {
+            cache.next_rebuild();
+        }
+    })
+}
+
+pub(super) fn cached(value: impl IntoJsValue) -> Value {
+    let cache = ACTIVE_CACHE
+        .with(|active| active.borrow().clone())
+        .unwrap_or_else(|| DEFAULT_CACHE.with(Rc::clone));
+    let cached = match cache.try_borrow_mut() {
+        Ok(mut cache) => cache.cached(value),
+        Err(_) => value.into_value(),
+    };
+    cached
+}
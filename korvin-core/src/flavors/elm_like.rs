@@ -1,43 +1,92 @@
 use crate::{
     element_builder::{AsElementBuilder, ElementBuilder},
-    web_sys::{self, HtmlInputElement, InputEvent, MouseEvent},
+    raw_operations,
+    web_sys::{
+        self, HtmlElement, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement, InputEvent,
+        MouseEvent,
+    },
 };
 use eyre::{eyre, ContextCompat, Result, WrapErr};
 use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender};
-use std::{cell::RefCell, str::FromStr};
+use std::{rc::Rc, str::FromStr};
 use wasm_bindgen::JsCast;
 
+pub mod cmd;
+pub mod debugger;
+pub mod form;
+pub mod html;
+pub mod persist;
+pub mod program;
+pub mod router;
 pub mod stream_compat;
+pub mod sub;
 
 pub trait InputEventExt {
+    /// The value of the `<input>`, `<textarea>` or `<select>` the event came from, or the text
+    /// of a contenteditable element.
     fn value<T: FromStr>(&self) -> Result<T>
     where
         <T as FromStr>::Err: std::fmt::Debug;
-    fn on_value<M, T: FromStr, F: Fn(T) -> M>(self, communicator: Communicator<M>, handle: F)
+    /// `.checked` of the checkbox or radio button the event came from.
+    fn checked(&self) -> Result<bool>;
+    /// Values of the selected options of the `<select>` the event came from.
+    fn selected_values(&self) -> Result<Vec<String>>;
+    fn on_value<M, T: FromStr, F: Fn(T) -> M>(self, communicator: &Communicator<M>, handle: F)
     where
         <T as FromStr>::Err: std::fmt::Debug,
         T: FromStr,
         F: Fn(T) -> M;
 }
 
+fn control_value(target: &web_sys::EventTarget) -> Result<String> {
+    if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+        Ok(input.value())
+    } else if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+        Ok(textarea.value())
+    } else if let Some(select) = target.dyn_ref::<HtmlSelectElement>() {
+        Ok(select.value())
+    } else {
+        target
+            .dyn_ref::<HtmlElement>()
+            .filter(|element| raw_operations::is_editable(element))
+            .context("not a form control")
+            .map(|element| element.text_content().unwrap_or_default())
+    }
+}
+
 impl InputEventExt for web_sys::InputEvent {
     fn value<T: FromStr>(&self) -> Result<T>
     where
         <T as FromStr>::Err: std::fmt::Debug,
     {
-        self.target().context("no target").and_then(|t| {
-            t.dyn_ref::<HtmlInputElement>()
-                .context("not an input element")
-                .and_then(|v| {
-                    v.value()
-                        .parse()
-                        .map_err(|e| eyre!("{e:?}"))
-                        .wrap_err("parsing value")
-                })
-        })
+        self.target()
+            .context("no target")
+            .and_then(|target| control_value(&target))
+            .and_then(|value| {
+                value
+                    .parse()
+                    .map_err(|e| eyre!("{e:?}"))
+                    .wrap_err("parsing value")
+            })
+    }
+
+    fn checked(&self) -> Result<bool> {
+        self.target()
+            .context("no target")?
+            .dyn_ref::<HtmlInputElement>()
+            .context("not an input element")
+            .map(|input| input.checked())
     }
 
-    fn on_value<M, T, F>(self, communicator: Communicator<M>, handle: F)
+    fn selected_values(&self) -> Result<Vec<String>> {
+        self.target()
+            .context("no target")?
+            .dyn_ref::<HtmlSelectElement>()
+            .context("not a select element")
+            .map(raw_operations::selected_values)
+    }
+
+    fn on_value<M, T, F>(self, communicator: &Communicator<M>, handle: F)
     where
         <T as FromStr>::Err: std::fmt::Debug,
         T: FromStr,
@@ -51,7 +100,7 @@ impl InputEventExt for web_sys::InputEvent {
 
 pub fn input<T, M, F>(
     key: impl std::hash::Hash,
-    communicator: Communicator<M>,
+    communicator: &Communicator<M>,
     value: T,
     callback: F,
 ) -> ElementBuilder
@@ -60,41 +109,208 @@ where
     <T as FromStr>::Err: std::fmt::Debug,
     F: Fn(T) -> M + 'static + Clone,
 {
+    control("input", key, communicator, value, callback)
+}
+
+pub fn textarea<T, M, F>(
+    key: impl std::hash::Hash,
+    communicator: &Communicator<M>,
+    value: T,
+    callback: F,
+) -> ElementBuilder
+where
+    T: FromStr + std::fmt::Display + 'static,
+    <T as FromStr>::Err: std::fmt::Debug,
+    F: Fn(T) -> M + 'static + Clone,
+{
+    control("textarea", key, communicator, value, callback)
+}
+
+/// A contenteditable `<div>` whose text is controlled like an input's value.
+pub fn editable<M, F>(
+    key: impl std::hash::Hash,
+    communicator: &Communicator<M>,
+    text: &str,
+    callback: F,
+) -> ElementBuilder
+where
+    F: Fn(String) -> M + 'static + Clone,
+{
+    control("div", key, communicator, text.to_string(), callback)
+        .attribute("contenteditable", "true")
+}
+
+fn control<T, M, F>(
+    kind: &str,
+    key: impl std::hash::Hash,
+    communicator: &Communicator<M>,
+    value: T,
+    callback: F,
+) -> ElementBuilder
+where
+    T: FromStr + std::fmt::Display + 'static,
+    <T as FromStr>::Err: std::fmt::Debug,
+    F: Fn(T) -> M + 'static + Clone,
+{
+    let communicator = communicator.clone();
+    kind.input_value(value.to_string())
+        .event(key, "input", move |event: InputEvent| {
+            event.on_value(&communicator, callback.clone())
+        })
+}
+
+/// `<input type="checkbox">`, `callback` receives the new `.checked`.
+pub fn checkbox<M, F>(
+    key: impl std::hash::Hash,
+    communicator: &Communicator<M>,
+    checked: bool,
+    callback: F,
+) -> ElementBuilder
+where
+    F: Fn(bool) -> M + 'static,
+{
+    let communicator = communicator.clone();
+    "input"
+        .attribute("type", "checkbox")
+        .checked(checked)
+        .event(key, "input", move |event: InputEvent| {
+            match event.checked() {
+                Ok(checked) => communicator.send(callback(checked)),
+                Err(message) => tracing::error!(?message, "bad checkbox"),
+            }
+        })
+}
+
+/// `<input type="radio">` of the group `name`, `callback` runs when it gets selected.
+pub fn radio<M, F>(
+    key: impl std::hash::Hash,
+    communicator: &Communicator<M>,
+    name: &str,
+    checked: bool,
+    callback: F,
+) -> ElementBuilder
+where
+    F: Fn() -> M + 'static,
+{
+    let communicator = communicator.clone();
     "input"
-        .input_value(value.to_string().as_str())
+        .attribute("type", "radio")
+        .attribute("name", name)
+        .checked(checked)
+        .event(key, "input", move |event: InputEvent| {
+            if event.checked().unwrap_or_default() {
+                communicator.send(callback())
+            }
+        })
+}
+
+fn options<'a>(
+    options: impl IntoIterator<Item = (&'a str, &'a str)>,
+    selected: impl Fn(&str) -> bool,
+) -> Vec<ElementBuilder> {
+    options
+        .into_iter()
+        .map(move |(value, label)| {
+            let option = "option".attribute("value", value).text(label).key(value);
+            // options are inserted after the select's value is set, the attribute covers the
+            // first render
+            match selected(value) {
+                true => option.attribute("selected", "selected"),
+                false => option,
+            }
+        })
+        .collect()
+}
+
+/// `<select>` with `(value, label)` options, `callback` receives the selected value.
+pub fn select<'a, M, F>(
+    key: impl std::hash::Hash,
+    communicator: &Communicator<M>,
+    choices: impl IntoIterator<Item = (&'a str, &'a str)>,
+    selected: &str,
+    callback: F,
+) -> ElementBuilder
+where
+    F: Fn(String) -> M + 'static + Clone,
+{
+    let communicator = communicator.clone();
+    "select"
+        .children(options(choices, |value| value == selected))
+        .input_value(selected)
         .event(key, "input", move |event: InputEvent| {
-            event.on_value(communicator, callback.clone())
+            event.on_value(&communicator, callback.clone())
+        })
+}
+
+/// `<select multiple>`, `callback` receives the values of all selected options.
+pub fn select_multiple<'a, M, F>(
+    key: impl std::hash::Hash,
+    communicator: &Communicator<M>,
+    choices: impl IntoIterator<Item = (&'a str, &'a str)>,
+    selected: &[&str],
+    callback: F,
+) -> ElementBuilder
+where
+    F: Fn(Vec<String>) -> M + 'static,
+{
+    let communicator = communicator.clone();
+    "select"
+        .attribute("multiple", "multiple")
+        .children(options(choices, |value| selected.contains(&value)))
+        .selected_values(selected.iter().copied())
+        .event(key, "input", move |event: InputEvent| {
+            match event.selected_values() {
+                Ok(values) => communicator.send(callback(values)),
+                Err(message) => tracing::error!(?message, "bad select"),
+            }
         })
 }
 
 pub fn button<M, F>(
     key: impl std::hash::Hash,
-    communicator: Communicator<M>,
+    communicator: &Communicator<M>,
     callback: F,
 ) -> ElementBuilder
 where
     F: (Fn() -> M) + 'static + Clone,
 {
+    let communicator = communicator.clone();
     "button".event(key, "mousedown", move |_: MouseEvent| {
         communicator.send(callback())
     })
 }
 
+/// The program has stopped, its message channel is closed.
+#[derive(Debug, thiserror::Error)]
+#[error("program has stopped, message was dropped")]
+pub struct ProgramStopped;
+
+/// Sends messages to the running program. Cloning is cheap, the channel is closed once the
+/// program stops.
 pub struct Communicator<M, N = M>
 where
     M: 'static,
 {
-    map: fn(N) -> M,
-    tx: &'static RefCell<UnboundedSender<M>>,
+    map: Rc<dyn Fn(N) -> M>,
+    tx: UnboundedSender<M>,
 }
 
 impl<M, N> Clone for Communicator<M, N> {
     fn clone(&self) -> Self {
-        *self
+        Self {
+            map: self.map.clone(),
+            tx: self.tx.clone(),
+        }
     }
 }
 
-impl<M, N> Copy for Communicator<M, N> {}
+impl<M, N> std::fmt::Debug for Communicator<M, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Communicator")
+            .field("closed", &self.tx.is_closed())
+            .finish_non_exhaustive()
+    }
+}
 
 impl<M> Communicator<M> {
     pub fn create() -> (UnboundedReceiver<M>, Self) {
@@ -102,24 +318,43 @@ impl<M> Communicator<M> {
         (
             rx,
             Self {
-                map: std::convert::identity,
-                tx: Box::leak(Box::new(RefCell::new(tx))),
+                map: Rc::new(std::convert::identity),
+                tx,
             },
         )
     }
 }
 
-impl<M, N> Communicator<M, N> {
-    pub fn map<O>(self, map: impl Into<fn(O) -> M>) -> Communicator<M, O> {
+impl<M, N: 'static> Communicator<M, N> {
+    /// Maps messages of a child view into this communicator's messages.
+    pub fn map<O: 'static>(&self, map: impl Fn(O) -> N + 'static) -> Communicator<M, O> {
+        let inner = self.map.clone();
         Communicator {
-            map: map.into(),
-            tx: self.tx,
+            map: Rc::new(move |message| inner(map(message))),
+            tx: self.tx.clone(),
         }
     }
-    pub fn send(self, message: N) {
+
+    pub fn try_send(&self, message: N) -> Result<(), ProgramStopped> {
         self.tx
-            .borrow_mut()
             .unbounded_send((self.map)(message))
-            .expect("runtime crashed, couldn't send a message")
+            .map_err(|_| ProgramStopped)
+    }
+
+    /// Like [`Communicator::try_send`], but drops the message once the program has stopped. Late
+    /// DOM events are expected after [`program::ProgramHandle::stop`], so it's only logged at
+    /// `debug`.
+    pub fn send(&self, message: N) {
+        if let Err(message) = self.try_send(message) {
+            tracing::debug!(%message, "sending message");
+        }
+    }
+
+    pub fn close(&self) {
+        self.tx.close_channel()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
     }
 }
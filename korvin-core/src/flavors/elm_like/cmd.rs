@@ -0,0 +1,153 @@
+use futures::{
+    future::{self, LocalBoxFuture},
+    Future, FutureExt,
+};
+use std::time::Duration;
+
+/// Where commands run. The [`super::program::Program`] uses [`WasmExecutor`], tests can provide
+/// their own to run commands natively and control time.
+pub trait Executor {
+    fn spawn(&self, task: LocalBoxFuture<'static, ()>);
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()>;
+}
+
+/// Spawns on `wasm_bindgen_futures` and sleeps with `setTimeout`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WasmExecutor;
+
+impl Executor for WasmExecutor {
+    fn spawn(&self, task: LocalBoxFuture<'static, ()>) {
+        wasm_bindgen_futures::spawn_local(task)
+    }
+
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()> {
+        let timeout = i32::try_from(duration.as_millis()).unwrap_or(i32::MAX);
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            let scheduled = web_sys::window().ok_or("no window").and_then(|window| {
+                window
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, timeout)
+                    .map_err(|_| "setTimeout failed")
+            });
+            if let Err(message) = scheduled {
+                tracing::error!(message, "sleeping failed, resolving immediately");
+                let _ = resolve.call0(&wasm_bindgen::JsValue::NULL);
+            }
+        });
+        wasm_bindgen_futures::JsFuture::from(promise)
+            .map(|_| ())
+            .boxed_local()
+    }
+}
+
+type Effect<M> = Box<dyn FnOnce(&dyn Executor) -> LocalBoxFuture<'static, Option<M>>>;
+
+/// Async work requested by `update`, its result is fed back to `update` as a message.
+#[must_use = "commands do nothing unless returned from update or run"]
+pub struct Cmd<M> {
+    effects: Vec<Effect<M>>,
+}
+
+impl<M: 'static> Default for Cmd<M> {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<M: 'static> Cmd<M> {
+    pub fn none() -> Self {
+        Self {
+            effects: Default::default(),
+        }
+    }
+
+    pub fn batch(cmds: impl IntoIterator<Item = Self>) -> Self {
+        Self {
+            effects: cmds.into_iter().flat_map(|cmd| cmd.effects).collect(),
+        }
+    }
+
+    /// Sends the output of the future as a message once it's done.
+    pub fn perform(future: impl Future<Output = M> + 'static) -> Self {
+        Self {
+            effects: vec![Box::new(move |_: &dyn Executor| {
+                future.map(Some).boxed_local()
+            })],
+        }
+    }
+
+    /// Runs `effect` when the command is executed, without producing a message.
+    pub fn effect(effect: impl FnOnce() + 'static) -> Self {
+        Self {
+            effects: vec![Box::new(move |_: &dyn Executor| {
+                effect();
+                future::ready(None).boxed_local()
+            })],
+        }
+    }
+
+    /// Sends `message` after `duration`.
+    pub fn delay(duration: Duration, message: M) -> Self {
+        Self {
+            effects: vec![Box::new(move |executor: &dyn Executor| {
+                executor
+                    .sleep(duration)
+                    .map(move |()| Some(message))
+                    .boxed_local()
+            })],
+        }
+    }
+
+    pub fn map<N: 'static>(self, map: impl Fn(M) -> N + Clone + 'static) -> Cmd<N> {
+        Cmd {
+            effects: self
+                .effects
+                .into_iter()
+                .map(|effect| -> Effect<N> {
+                    let map = map.clone();
+                    Box::new(move |executor: &dyn Executor| {
+                        effect(executor)
+                            .map(move |message| message.map(map))
+                            .boxed_local()
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Spawns every effect on the executor, `send` receives the resulting messages.
+    pub fn run(self, executor: &dyn Executor, send: impl Fn(M) + Clone + 'static) {
+        self.effects.into_iter().for_each(|effect| {
+            let send = send.clone();
+            let task = effect(executor);
+            executor.spawn(
+                async move {
+                    if let Some(message) = task.await {
+                        send(message)
+                    }
+                }
+                .boxed_local(),
+            )
+        })
+    }
+
+    /// Runs every effect concurrently and collects the messages in the order they were produced.
+    pub async fn collect(self, executor: &dyn Executor) -> Vec<M> {
+        let tasks = self
+            .effects
+            .into_iter()
+            .map(|effect| effect(executor))
+            .collect::<Vec<_>>();
+        let mut messages = vec![];
+        let mut pending = tasks;
+        while !pending.is_empty() {
+            let (message, _, rest) = future::select_all(pending).await;
+            messages.extend(message);
+            pending = rest;
+        }
+        messages
+    }
+}
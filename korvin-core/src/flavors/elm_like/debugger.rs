@@ -0,0 +1,138 @@
+use super::html::Html;
+use crate::{element_builder::AsElementBuilder, web_sys::MouseEvent};
+use std::collections::VecDeque;
+
+/// Oldest entries are forgotten past this point.
+const MAX_HISTORY: usize = 1000;
+
+const OVERLAY_STYLE: &str = "position: fixed; bottom: 0; right: 0; z-index: 2147483647; \
+     max-height: 40vh; width: 24rem; overflow: auto; font: 12px monospace; \
+     background: #222; color: #eee; padding: 0.5rem;";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DebugMessage {
+    Back,
+    Forward,
+    /// to the entry with that number, entries are numbered from `init` on and keep their number
+    /// when older ones are forgotten
+    Jump(usize),
+    /// go back to the current model, new app messages always do that as well
+    Live,
+}
+
+struct Entry<Model> {
+    number: usize,
+    message: String,
+    model: Model,
+}
+
+/// Message log of a [`super::program::Program`] with a snapshot of the model after every
+/// message. Looking at an older entry only re-runs `view` against its snapshot, `update` and
+/// the commands it returned are never executed again.
+pub(crate) struct Debugger<Model, Msg> {
+    snapshot: fn(&Model) -> Model,
+    describe: fn(&Msg) -> String,
+    entries: VecDeque<Entry<Model>>,
+    /// `None` when looking at the live model
+    position: Option<usize>,
+    recorded: usize,
+}
+
+impl<Model, Msg> Debugger<Model, Msg> {
+    pub(crate) fn new() -> Self
+    where
+        Model: Clone,
+        Msg: std::fmt::Debug,
+    {
+        Self {
+            snapshot: Model::clone,
+            describe: |message| format!("{message:?}"),
+            entries: Default::default(),
+            position: None,
+            recorded: 0,
+        }
+    }
+
+    pub(crate) fn describe(&self, message: &Msg) -> String {
+        (self.describe)(message)
+    }
+
+    pub(crate) fn record(&mut self, message: String, model: &Model) {
+        if self.entries.len() == MAX_HISTORY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Entry {
+            number: self.recorded,
+            message,
+            model: (self.snapshot)(model),
+        });
+        self.recorded += 1;
+        self.position = None;
+    }
+
+    pub(crate) fn handle(&mut self, message: DebugMessage) {
+        let last = self.entries.len().saturating_sub(1);
+        let current = self.position.unwrap_or(last);
+        self.position = match message {
+            DebugMessage::Back => Some(current.saturating_sub(1)),
+            DebugMessage::Forward => Some(current + 1),
+            DebugMessage::Jump(number) => self
+                .entries
+                .front()
+                .and_then(|first| number.checked_sub(first.number)),
+            DebugMessage::Live => None,
+        }
+        .filter(|position| *position < last);
+    }
+
+    /// The model `view` should be called with.
+    pub(crate) fn viewed<'a>(&'a self, live: &'a Model) -> &'a Model {
+        self.position
+            .and_then(|position| self.entries.get(position))
+            .map(|entry| &entry.model)
+            .unwrap_or(live)
+    }
+
+    pub(crate) fn overlay(&self) -> Html<DebugMessage> {
+        let last = self.entries.len().saturating_sub(1);
+        let current = self.position.unwrap_or(last);
+        let button = |text: &str, message: DebugMessage| {
+            Html::from("button")
+                .text(text)
+                .on(message, "click", move |_: MouseEvent| message)
+        };
+        let number = |position: usize| {
+            self.entries
+                .get(position)
+                .map_or(position, |entry| entry.number)
+        };
+        let status = match self.position {
+            Some(position) => format!("viewing {} of {}", number(position), number(last)),
+            None => format!("live, {} messages", number(last)),
+        };
+        let first = self.entries.front().map_or(0, |entry| entry.number);
+        let entries = self.entries.iter().enumerate().map(|(index, entry)| {
+            let style = match index == current {
+                true => "cursor: pointer; background: #555",
+                false => "cursor: pointer",
+            };
+            let number = entry.number;
+            // numbers don't shift when the oldest entries are dropped, unlike indices
+            Html::from("li")
+                .key(number)
+                .attribute("style", style)
+                .text(format!("{number}: {}", entry.message))
+                .on(number, "click", move |_: MouseEvent| {
+                    DebugMessage::Jump(number)
+                })
+        });
+        Html::from("aside")
+            .attribute("class", "korvin-debugger")
+            .attribute("style", OVERLAY_STYLE)
+            .child("div".text(status.as_str()))
+            .child(button("back", DebugMessage::Back))
+            .child(button("forward", DebugMessage::Forward))
+            .child(button("live", DebugMessage::Live))
+            .child(Html::from("ol").attribute("start", first).children(entries))
+    }
+}
@@ -0,0 +1,302 @@
+use super::{html::Html, InputEventExt};
+use crate::{element_builder::AsElementBuilder, web_sys::InputEvent};
+use std::{collections::BTreeMap, fmt::Display, rc::Rc, str::FromStr};
+
+type Check<T> = Rc<dyn Fn(&T) -> Result<(), String>>;
+
+/// Checks a parsed value, `Err` holds the message shown next to the field.
+pub struct Validator<T>(Check<T>);
+
+impl<T> Clone for Validator<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: 'static> Validator<T> {
+    pub fn new(check: impl Fn(&T) -> Result<(), String> + 'static) -> Self {
+        Self(Rc::new(check))
+    }
+
+    /// Fails with `message` unless `predicate` holds.
+    pub fn check(predicate: impl Fn(&T) -> bool + 'static, message: impl Display) -> Self {
+        let message = message.to_string();
+        Self::new(move |value| {
+            predicate(value)
+                .then_some(())
+                .ok_or_else(|| message.clone())
+        })
+    }
+
+    /// Stops at the first failing validator.
+    pub fn and(self, other: Self) -> Self {
+        Self::new(move |value| (self.0)(value).and_then(|()| (other.0)(value)))
+    }
+
+    pub fn validate(&self, value: &T) -> Result<(), String> {
+        (self.0)(value)
+    }
+}
+
+pub mod validators {
+    use super::Validator;
+    use std::fmt::Display;
+
+    pub fn min<T: PartialOrd + Display + 'static>(min: T) -> Validator<T> {
+        let message = format!("must be at least {min}");
+        Validator::check(move |value| *value >= min, message)
+    }
+
+    pub fn max<T: PartialOrd + Display + 'static>(max: T) -> Validator<T> {
+        let message = format!("must be at most {max}");
+        Validator::check(move |value| *value <= max, message)
+    }
+
+    pub fn non_empty<T: AsRef<str> + 'static>() -> Validator<T> {
+        Validator::check(|value: &T| !value.as_ref().trim().is_empty(), "required")
+    }
+
+    pub fn max_length<T: AsRef<str> + 'static>(max: usize) -> Validator<T> {
+        Validator::check(
+            move |value: &T| value.as_ref().chars().count() <= max,
+            format!("at most {max} characters"),
+        )
+    }
+}
+
+/// Input text together with what it parses to and everything wrong with it.
+///
+/// The raw text is kept even when it doesn't parse, so the user can keep typing instead of
+/// having the input reset to the last valid value.
+pub struct Field<T> {
+    raw: String,
+    value: Option<T>,
+    errors: Vec<String>,
+    touched: bool,
+    optional: bool,
+    validators: Vec<Validator<T>>,
+}
+
+impl<T: Clone> Clone for Field<T> {
+    fn clone(&self) -> Self {
+        Self {
+            raw: self.raw.clone(),
+            value: self.value.clone(),
+            errors: self.errors.clone(),
+            touched: self.touched,
+            optional: self.optional,
+            validators: self.validators.clone(),
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Field<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Field")
+            .field("raw", &self.raw)
+            .field("value", &self.value)
+            .field("errors", &self.errors)
+            .field("touched", &self.touched)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Field<T>
+where
+    T: FromStr + 'static,
+    T::Err: Display,
+{
+    pub fn new(raw: impl Into<String>) -> Self {
+        let mut field = Self {
+            raw: Default::default(),
+            value: None,
+            errors: Default::default(),
+            touched: false,
+            optional: false,
+            validators: Default::default(),
+        };
+        field.update(raw.into());
+        field
+    }
+
+    pub fn with_value(value: T) -> Self
+    where
+        T: Display,
+    {
+        Self::new(value.to_string())
+    }
+
+    /// Blank input is fine and has no value instead of being a parse error.
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self.revalidate();
+        self
+    }
+
+    pub fn validate(mut self, validator: Validator<T>) -> Self {
+        self.validators.push(validator);
+        self.revalidate();
+        self
+    }
+
+    /// Call with the text of every input event.
+    pub fn set(&mut self, raw: impl Into<String>) {
+        self.touched = true;
+        self.update(raw.into());
+    }
+
+    /// Marks the field as edited so its errors get shown, e.g. when the form is submitted.
+    pub fn touch(&mut self) {
+        self.touched = true;
+    }
+
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    /// The parsed value, even when a validator rejected it.
+    pub fn value(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn touched(&self) -> bool {
+        self.touched
+    }
+
+    /// Errors worth showing: none until the user edited the field.
+    pub fn visible_errors(&self) -> &[String] {
+        match self.touched {
+            true => &self.errors,
+            false => &[],
+        }
+    }
+
+    fn update(&mut self, raw: String) {
+        self.raw = raw;
+        self.revalidate();
+    }
+
+    fn revalidate(&mut self) {
+        self.errors.clear();
+        self.value = None;
+        if self.optional && self.raw.trim().is_empty() {
+            return;
+        }
+        match self.raw.trim().parse::<T>() {
+            Ok(value) => {
+                self.errors.extend(
+                    self.validators
+                        .iter()
+                        .filter_map(|validator| validator.validate(&value).err()),
+                );
+                self.value = Some(value);
+            }
+            Err(error) => self.errors.push(error.to_string()),
+        }
+    }
+
+    /// An `<input>` showing the raw text, sending every change through `message`.
+    pub fn input<M: 'static>(
+        &self,
+        key: impl std::hash::Hash,
+        message: impl Fn(String) -> M + 'static,
+    ) -> Html<M> {
+        Html::from("input")
+            .input_value(self.raw.as_str())
+            .attribute(
+                "aria-invalid",
+                match self.visible_errors().is_empty() {
+                    true => "false",
+                    false => "true",
+                },
+            )
+            .on_maybe(key, "input", move |event: InputEvent| {
+                match event.value::<String>() {
+                    Ok(raw) => Some(message(raw)),
+                    Err(message) => {
+                        tracing::error!(?message, "reading input");
+                        None
+                    }
+                }
+            })
+    }
+
+    /// A list of the visible errors, empty until the field was touched.
+    pub fn errors_list<M: 'static>(&self) -> Html<M> {
+        Html::from("ul")
+            .attribute("class", "field-errors")
+            .children(
+                self.visible_errors()
+                    .iter()
+                    .map(|error| "li".text(error.as_str())),
+            )
+    }
+}
+
+/// Errors of every invalid field, by field name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormErrors(pub BTreeMap<&'static str, Vec<String>>);
+
+impl Display for FormErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0
+            .iter()
+            .try_for_each(|(field, errors)| writeln!(f, "{field}: {}", errors.join(", ")))
+    }
+}
+
+impl std::error::Error for FormErrors {}
+
+/// Collects field values while building the submitted struct.
+#[derive(Debug, Default)]
+pub struct Submission {
+    errors: FormErrors,
+}
+
+impl Submission {
+    /// `None` (with the errors recorded) when the field is invalid.
+    pub fn field<T: Clone>(&mut self, name: &'static str, field: &Field<T>) -> Option<T> {
+        match (field.errors.is_empty(), field.value.as_ref()) {
+            (true, value) => value.cloned(),
+            (false, _) => {
+                self.errors.0.insert(name, field.errors.clone());
+                None
+            }
+        }
+    }
+
+    /// Like [`Submission::field`] for fields that must have a value even when optional.
+    pub fn required<T: Clone>(&mut self, name: &'static str, field: &Field<T>) -> Option<T> {
+        let value = self.field(name, field);
+        if value.is_none() && field.errors.is_empty() {
+            self.errors.0.insert(name, vec!["required".to_string()]);
+        }
+        value
+    }
+}
+
+/// Builds `T` out of the fields. Read every field into a variable before combining them with
+/// `?` so that all invalid fields get reported, not only the first one.
+///
+/// ```ignore
+/// form::submit(|form| {
+///     let name = form.field("name", &model.name);
+///     let age = form.field("age", &model.age);
+///     Some(Person { name: name?, age: age? })
+/// })
+/// ```
+pub fn submit<T>(build: impl FnOnce(&mut Submission) -> Option<T>) -> Result<T, FormErrors> {
+    let mut submission = Submission::default();
+    match build(&mut submission) {
+        Some(value) if submission.errors.0.is_empty() => Ok(value),
+        _ => Err(submission.errors),
+    }
+}
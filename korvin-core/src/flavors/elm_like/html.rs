@@ -0,0 +1,210 @@
+use super::Communicator;
+use crate::{
+    data::PropertyValue,
+    element_builder::{
+        calculate_hash,
+        value_cache::{IntoJsValue, IntoOptionalJsValue},
+        AsElementBuilder, ElementBuilder,
+    },
+    mutation::{
+        element::builder_mutation::modify::add_event_listener::{
+            by_event_kind, ElementAddEventListenerMutation,
+        },
+        traits::Perform,
+    },
+    style::Stylesheet,
+};
+use std::{hash::Hash, rc::Rc};
+use wasm_bindgen::convert::{FromWasmAbi, RefFromWasmAbi};
+
+type Dispatch<M> = Rc<dyn Fn(M)>;
+type Deferred<M> = Box<dyn FnOnce(ElementBuilder, &Dispatch<M>) -> ElementBuilder>;
+
+/// A view whose event handlers produce messages of type `M` instead of sending them through a
+/// [`Communicator`]. Child views written for their own message type are embedded with
+/// [`Html::map`].
+///
+/// ```ignore
+/// fn counter(count: i32) -> Html<CounterMessage> {
+///     Html::from("button")
+///         .text(count)
+///         .on((), "click", |_: MouseEvent| CounterMessage::Increment)
+/// }
+///
+/// Html::from("main").child(counter(model.count).map(AppMessage::Counter))
+/// ```
+pub struct Html<M> {
+    element: ElementBuilder,
+    deferred: Vec<Deferred<M>>,
+}
+
+impl<M: 'static> Html<M> {
+    pub fn text(self, text: impl IntoOptionalJsValue) -> Self {
+        self.eager(|element| element.text(text))
+    }
+
+    pub fn input_value(self, value: impl IntoOptionalJsValue) -> Self {
+        self.eager(|element| element.input_value(value))
+    }
+
+    pub fn selected_values<'a>(self, values: impl IntoIterator<Item = &'a str>) -> Self {
+        self.eager(|element| element.selected_values(values))
+    }
+
+    pub fn checked(self, checked: bool) -> Self {
+        self.eager(|element| element.checked(checked))
+    }
+
+    pub fn attribute(self, attribute: impl IntoJsValue, value: impl IntoOptionalJsValue) -> Self {
+        self.eager(|element| element.attribute(attribute, value))
+    }
+
+    pub fn property(self, property: impl IntoJsValue, value: impl Into<PropertyValue>) -> Self {
+        self.eager(|element| element.property(property, value))
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        self.eager(|element| element.class(class))
+    }
+
+    pub fn class_if(self, class: &str, enabled: bool) -> Self {
+        self.eager(|element| element.class_if(class, enabled))
+    }
+
+    pub fn style(self, property: impl IntoJsValue, value: impl IntoOptionalJsValue) -> Self {
+        self.eager(|element| element.style(property, value))
+    }
+
+    pub fn stylesheet(self, stylesheet: &Stylesheet) -> Self {
+        self.eager(|element| element.stylesheet(stylesheet))
+    }
+
+    pub fn template(self) -> Self {
+        self.eager(|element| element.template())
+    }
+
+    pub fn key(self, key: impl Hash) -> Self {
+        self.eager(|element| element.key(key))
+    }
+
+    /// Sends the message returned by `handler` for every `name` event.
+    pub fn on<EventKind>(
+        self,
+        key: impl Hash,
+        name: &'static str,
+        handler: impl Fn(EventKind) -> M + 'static,
+    ) -> Self
+    where
+        EventKind: std::fmt::Debug + Sized + RefFromWasmAbi + FromWasmAbi + 'static,
+        by_event_kind::ElementAddEventListenerMutation<EventKind>:
+            Into<ElementAddEventListenerMutation> + Perform,
+    {
+        self.on_maybe(key, name, move |event| Some(handler(event)))
+    }
+
+    /// Like [`Html::on`], but `None` sends nothing.
+    pub fn on_maybe<EventKind>(
+        self,
+        key: impl Hash,
+        name: &'static str,
+        handler: impl Fn(EventKind) -> Option<M> + 'static,
+    ) -> Self
+    where
+        EventKind: std::fmt::Debug + Sized + RefFromWasmAbi + FromWasmAbi + 'static,
+        by_event_kind::ElementAddEventListenerMutation<EventKind>:
+            Into<ElementAddEventListenerMutation> + Perform,
+    {
+        let key = calculate_hash(&key);
+        self.deferred(move |element, dispatch| {
+            let dispatch = dispatch.clone();
+            element.event(key, name, move |event: EventKind| {
+                if let Some(message) = handler(event) {
+                    dispatch(message)
+                }
+            })
+        })
+    }
+
+    pub fn child(self, child: impl Into<Html<M>>) -> Self {
+        let child = child.into();
+        self.deferred(move |element, dispatch| element.child(child.render_with(dispatch)))
+    }
+
+    pub fn children(self, children: impl IntoIterator<Item = impl Into<Html<M>>>) -> Self {
+        children
+            .into_iter()
+            .fold(self, |html, child| html.child(child))
+    }
+
+    /// See [`AsElementBuilder::keyed_children`].
+    pub fn keyed_children<Item, Key: Hash, Child: Into<Html<M>>>(
+        self,
+        items: impl IntoIterator<Item = Item>,
+        key: impl Fn(&Item) -> Key,
+        render: impl Fn(Item) -> Child,
+    ) -> Self {
+        self.children(items.into_iter().map(|item| {
+            let key = key(&item);
+            render(item).into().key(key)
+        }))
+    }
+
+    /// Lifts a child view into the parent's message space.
+    pub fn map<N: 'static>(self, map: impl Fn(M) -> N + 'static) -> Html<N> {
+        let Self { element, deferred } = self;
+        Html {
+            element,
+            deferred: vec![Box::new(move |element, dispatch: &Dispatch<N>| {
+                let parent = dispatch.clone();
+                let dispatch: Dispatch<M> = Rc::new(move |message| parent(map(message)));
+                deferred
+                    .into_iter()
+                    .fold(element, |element, deferred| deferred(element, &dispatch))
+            })],
+        }
+    }
+
+    /// Produces the element with every handler sending through `communicator`.
+    pub fn render<Root>(self, communicator: &Communicator<Root, M>) -> ElementBuilder {
+        let communicator = communicator.clone();
+        self.render_with(&(Rc::new(move |message| communicator.send(message)) as Dispatch<M>))
+    }
+
+    fn render_with(self, dispatch: &Dispatch<M>) -> ElementBuilder {
+        self.deferred
+            .into_iter()
+            .fold(self.element, |element, deferred| {
+                deferred(element, dispatch)
+            })
+    }
+
+    fn eager(self, modify: impl FnOnce(ElementBuilder) -> ElementBuilder) -> Self {
+        Self {
+            element: modify(self.element),
+            ..self
+        }
+    }
+
+    fn deferred(
+        mut self,
+        deferred: impl FnOnce(ElementBuilder, &Dispatch<M>) -> ElementBuilder + 'static,
+    ) -> Self {
+        self.deferred.push(Box::new(deferred));
+        self
+    }
+}
+
+impl<M> From<ElementBuilder> for Html<M> {
+    fn from(element: ElementBuilder) -> Self {
+        Self {
+            element,
+            deferred: Default::default(),
+        }
+    }
+}
+
+impl<M> From<&str> for Html<M> {
+    fn from(kind: &str) -> Self {
+        ElementBuilder::from(kind).into()
+    }
+}
@@ -0,0 +1,319 @@
+use super::cmd::Executor;
+use eyre::{bail, eyre, Result, WrapErr};
+use futures::{
+    channel::oneshot,
+    future::{self, LocalBoxFuture},
+    FutureExt,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    rc::Rc,
+    time::Duration,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{IdbDatabase, IdbOpenDbRequest, IdbRequest, IdbTransactionMode};
+
+/// Where serialized models are kept.
+pub trait Store {
+    fn load(&self, key: &str) -> LocalBoxFuture<'static, Result<Option<String>>>;
+    fn save(&self, key: &str, value: String) -> LocalBoxFuture<'static, Result<()>>;
+}
+
+fn js_error(error: JsValue) -> eyre::Report {
+    eyre!("{error:?}")
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalStorage;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionStorage;
+
+fn web_storage(session: bool) -> Result<web_sys::Storage> {
+    let window = web_sys::window().ok_or_else(|| eyre!("no window"))?;
+    match session {
+        true => window.session_storage(),
+        false => window.local_storage(),
+    }
+    .map_err(js_error)?
+    .ok_or_else(|| eyre!("storage is not available"))
+}
+
+fn load_from_web_storage(session: bool, key: &str) -> Result<Option<String>> {
+    web_storage(session)?.get_item(key).map_err(js_error)
+}
+
+fn save_to_web_storage(session: bool, key: &str, value: &str) -> Result<()> {
+    web_storage(session)?.set_item(key, value).map_err(js_error)
+}
+
+impl Store for LocalStorage {
+    fn load(&self, key: &str) -> LocalBoxFuture<'static, Result<Option<String>>> {
+        future::ready(load_from_web_storage(false, key)).boxed_local()
+    }
+
+    fn save(&self, key: &str, value: String) -> LocalBoxFuture<'static, Result<()>> {
+        future::ready(save_to_web_storage(false, key, &value)).boxed_local()
+    }
+}
+
+impl Store for SessionStorage {
+    fn load(&self, key: &str) -> LocalBoxFuture<'static, Result<Option<String>>> {
+        future::ready(load_from_web_storage(true, key)).boxed_local()
+    }
+
+    fn save(&self, key: &str, value: String) -> LocalBoxFuture<'static, Result<()>> {
+        future::ready(save_to_web_storage(true, key, &value)).boxed_local()
+    }
+}
+
+/// Keeps models in a single object store of the given database.
+#[derive(Debug, Clone)]
+pub struct IndexedDb {
+    database: String,
+}
+
+impl IndexedDb {
+    const OBJECT_STORE: &'static str = "models";
+
+    pub fn new(database: impl Into<String>) -> Self {
+        Self {
+            database: database.into(),
+        }
+    }
+
+    async fn open(database: String) -> Result<IdbDatabase> {
+        let factory = web_sys::window()
+            .ok_or_else(|| eyre!("no window"))?
+            .indexed_db()
+            .map_err(js_error)?
+            .ok_or_else(|| eyre!("IndexedDB is not available"))?;
+        let request: IdbOpenDbRequest = factory.open_with_u32(&database, 1).map_err(js_error)?;
+        let on_upgrade_needed = Closure::<dyn FnMut(web_sys::Event)>::new({
+            let request = request.clone();
+            move |_: web_sys::Event| {
+                let created = request
+                    .result()
+                    .and_then(|database| database.dyn_into::<IdbDatabase>())
+                    .and_then(|database| database.create_object_store(Self::OBJECT_STORE));
+                if let Err(message) = created {
+                    tracing::error!(?message, "creating object store");
+                }
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade_needed.as_ref().unchecked_ref()));
+        let database = completed(&request).await;
+        request.set_onupgradeneeded(None);
+        database?
+            .dyn_into::<IdbDatabase>()
+            .map_err(|_| eyre!("not a database"))
+    }
+
+    async fn request(
+        database: String,
+        perform: impl FnOnce(web_sys::IdbObjectStore) -> Result<IdbRequest, JsValue>,
+    ) -> Result<JsValue> {
+        let database = Self::open(database).await.wrap_err("opening database")?;
+        let request = database
+            .transaction_with_str_and_mode(Self::OBJECT_STORE, IdbTransactionMode::Readwrite)
+            .and_then(|transaction| transaction.object_store(Self::OBJECT_STORE))
+            .and_then(perform)
+            .map_err(js_error)?;
+        let result = completed(&request).await;
+        database.close();
+        result
+    }
+}
+
+/// Waits for the `success` or `error` event of the request.
+async fn completed(request: &IdbRequest) -> Result<JsValue> {
+    let (tx, rx) = oneshot::channel::<bool>();
+    let tx = Rc::new(RefCell::new(Some(tx)));
+    let callback = |succeeded: bool| {
+        let tx = tx.clone();
+        Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+            if let Some(tx) = tx.borrow_mut().take() {
+                let _ = tx.send(succeeded);
+            }
+        })
+    };
+    let on_success = callback(true);
+    let on_error = callback(false);
+    request.set_onsuccess(Some(on_success.as_ref().unchecked_ref()));
+    request.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+    let succeeded = rx.await;
+    request.set_onsuccess(None);
+    request.set_onerror(None);
+    match succeeded {
+        Ok(true) => request.result().map_err(js_error),
+        Ok(false) => Err(eyre!("{:?}", request.error())),
+        Err(oneshot::Canceled) => Err(eyre!("request was dropped")),
+    }
+}
+
+impl Store for IndexedDb {
+    fn load(&self, key: &str) -> LocalBoxFuture<'static, Result<Option<String>>> {
+        let key = JsValue::from_str(key);
+        Self::request(self.database.clone(), move |store| store.get(&key))
+            .map(|value| value.map(|value| value.as_string()))
+            .boxed_local()
+    }
+
+    fn save(&self, key: &str, value: String) -> LocalBoxFuture<'static, Result<()>> {
+        let key = JsValue::from_str(key);
+        Self::request(self.database.clone(), move |store| {
+            store.put_with_key(&JsValue::from_str(&value), &key)
+        })
+        .map(|result| result.map(|_| ()))
+        .boxed_local()
+    }
+}
+
+/// Keeps everything in memory, clones share the same contents.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStore {
+    values: Rc<RefCell<BTreeMap<String, String>>>,
+}
+
+impl MemoryStore {
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: impl Into<String>, value: impl Into<String>) {
+        self.values.borrow_mut().insert(key.into(), value.into());
+    }
+}
+
+impl Store for MemoryStore {
+    fn load(&self, key: &str) -> LocalBoxFuture<'static, Result<Option<String>>> {
+        future::ready(Ok(self.get(key))).boxed_local()
+    }
+
+    fn save(&self, key: &str, value: String) -> LocalBoxFuture<'static, Result<()>> {
+        self.insert(key, value);
+        future::ready(Ok(())).boxed_local()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    version: u32,
+    model: serde_json::Value,
+}
+
+type Migration = Box<dyn Fn(serde_json::Value) -> Result<serde_json::Value>>;
+
+/// Saves the model under `key` after updates and restores it when the program starts.
+///
+/// The model is stored together with its schema version. When an older version is found, the
+/// migrations registered for every version in between run in order before deserializing.
+pub struct Persistence {
+    key: String,
+    version: u32,
+    migrations: BTreeMap<u32, Migration>,
+    store: Rc<dyn Store>,
+    debounce: Duration,
+    pending: Rc<Cell<u64>>,
+}
+
+impl Persistence {
+    pub fn new(key: impl Into<String>, store: impl Store + 'static) -> Self {
+        Self {
+            key: key.into(),
+            version: 0,
+            migrations: Default::default(),
+            store: Rc::new(store),
+            debounce: Duration::from_millis(300),
+            pending: Default::default(),
+        }
+    }
+
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Turns a model stored with version `from` into one of version `from + 1`.
+    pub fn migration(
+        mut self,
+        from: u32,
+        migrate: impl Fn(serde_json::Value) -> Result<serde_json::Value> + 'static,
+    ) -> Self {
+        self.migrations.insert(from, Box::new(migrate));
+        self
+    }
+
+    /// Only the last model of a burst of updates is saved, once they stop for this long.
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// `None` when nothing was stored yet.
+    pub async fn restore<Model: DeserializeOwned>(&self) -> Result<Option<Model>> {
+        let Some(stored) = self.store.load(&self.key).await.wrap_err("loading model")? else {
+            return Ok(None);
+        };
+        self.decode(&stored)
+            .wrap_err_with(|| format!("restoring [{}]", self.key))
+            .map(Some)
+    }
+
+    pub fn decode<Model: DeserializeOwned>(&self, stored: &str) -> Result<Model> {
+        let Envelope { version, model } =
+            serde_json::from_str(stored).wrap_err("parsing stored model")?;
+        if version > self.version {
+            bail!("stored version {version} is newer than {}", self.version);
+        }
+        let model = (version..self.version).try_fold(model, |model, version| {
+            let migrate = self
+                .migrations
+                .get(&version)
+                .ok_or_else(|| eyre!("no migration from version {version}"))?;
+            migrate(model).wrap_err_with(|| format!("migrating from version {version}"))
+        })?;
+        serde_json::from_value(model).wrap_err("deserializing model")
+    }
+
+    pub fn encode<Model: Serialize>(&self, model: &Model) -> Result<String> {
+        serde_json::to_value(model)
+            .map(|model| Envelope {
+                version: self.version,
+                model,
+            })
+            .and_then(|envelope| serde_json::to_string(&envelope))
+            .wrap_err("serializing model")
+    }
+
+    /// Saves the model once no other save was requested for the debounce duration.
+    pub fn save<Model: Serialize>(&self, model: &Model, executor: &dyn Executor) {
+        let encoded = match self.encode(model) {
+            Ok(encoded) => encoded,
+            Err(message) => {
+                tracing::error!(?message, key = %self.key, "not saving model");
+                return;
+            }
+        };
+        let generation = self.pending.get().wrapping_add(1);
+        self.pending.set(generation);
+        let pending = self.pending.clone();
+        let store = self.store.clone();
+        let key = self.key.clone();
+        let sleep = executor.sleep(self.debounce);
+        executor.spawn(
+            async move {
+                sleep.await;
+                if pending.get() != generation {
+                    return;
+                }
+                if let Err(message) = store.save(&key, encoded).await {
+                    tracing::error!(?message, %key, "saving model failed");
+                }
+            }
+            .boxed_local(),
+        )
+    }
+}
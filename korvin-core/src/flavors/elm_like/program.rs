@@ -0,0 +1,298 @@
+use super::{
+    cmd::{Cmd, Executor, WasmExecutor},
+    debugger::{DebugMessage, Debugger},
+    html::Html,
+    persist::Persistence,
+    sub::{RunningSubscriptions, Sub},
+    Communicator,
+};
+use crate::{
+    element_builder::{AsElementBuilder, ElementBuilder, ElementWithChildrenRecipe},
+    web_sys::Element,
+    Runtime,
+};
+use eyre::{eyre, Result, WrapErr};
+use futures::{
+    channel::oneshot,
+    future::{Either, LocalBoxFuture},
+    FutureExt, StreamExt,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::rc::Rc;
+
+/// The init -> view -> receive message -> update -> rebuild loop.
+///
+/// ```ignore
+/// Program::new(Counter::default, Counter::update, view)
+///     .subscriptions(|_| Sub::none())
+///     .mount("body")?;
+/// ```
+pub struct Program<Model, Msg: 'static> {
+    init: Box<dyn FnOnce() -> Model>,
+    update: UpdateFn<Model, Msg>,
+    view: ViewFn<Model, Msg>,
+    subscriptions: SubscriptionsFn<Model, Msg>,
+    executor: Rc<dyn Executor>,
+    debugger: Option<Debugger<Model, Msg>>,
+    persistence: Option<PersistenceHooks<Model>>,
+}
+
+type UpdateFn<Model, Msg> = Box<dyn Fn(&mut Model, Msg) -> Cmd<Msg>>;
+type ViewFn<Model, Msg> = Box<dyn Fn(&Model, Communicator<Msg>) -> ElementBuilder>;
+type SubscriptionsFn<Model, Msg> = Box<dyn Fn(&Model) -> Sub<Msg>>;
+
+type RestoreFn<Model> = Box<dyn FnOnce() -> LocalBoxFuture<'static, Option<Model>>>;
+type SaveFn<Model> = Box<dyn Fn(&Model, &dyn Executor)>;
+
+struct PersistenceHooks<Model> {
+    restore: RestoreFn<Model>,
+    save: SaveFn<Model>,
+}
+
+/// Stops the program it was returned for. Dropping it leaves the program running.
+#[derive(Debug)]
+pub struct ProgramHandle {
+    stop: oneshot::Sender<()>,
+}
+
+impl ProgramHandle {
+    /// Stops processing messages, closes the program's [`Communicator`]s and removes everything
+    /// the program rendered.
+    pub fn stop(self) {
+        if self.stop.send(()).is_err() {
+            tracing::warn!("program was already stopped");
+        }
+    }
+}
+
+impl<Model, Msg> Program<Model, Msg>
+where
+    Model: 'static,
+    Msg: 'static,
+{
+    pub fn new(
+        init: impl FnOnce() -> Model + 'static,
+        update: impl Fn(&mut Model, Msg) -> Cmd<Msg> + 'static,
+        view: impl Fn(&Model, Communicator<Msg>) -> ElementBuilder + 'static,
+    ) -> Self {
+        Self {
+            init: Box::new(init),
+            update: Box::new(update),
+            view: Box::new(view),
+            subscriptions: Box::new(|_| Sub::none()),
+            executor: Rc::new(WasmExecutor),
+            debugger: None,
+            persistence: None,
+        }
+    }
+
+    /// Like [`Program::new`], but the view produces messages through [`Html`] event handlers.
+    pub fn html(
+        init: impl FnOnce() -> Model + 'static,
+        update: impl Fn(&mut Model, Msg) -> Cmd<Msg> + 'static,
+        view: impl Fn(&Model) -> Html<Msg> + 'static,
+    ) -> Self {
+        Self::new(init, update, move |model, communicator| {
+            view(model).render(&communicator)
+        })
+    }
+
+    pub fn subscriptions(mut self, subscriptions: impl Fn(&Model) -> Sub<Msg> + 'static) -> Self {
+        self.subscriptions = Box::new(subscriptions);
+        self
+    }
+
+    /// Replaces the [`WasmExecutor`] commands returned from `update` and intervals are run on.
+    pub fn executor(mut self, executor: impl Executor + 'static) -> Self {
+        self.executor = Rc::new(executor);
+        self
+    }
+
+    /// Records every message together with a snapshot of the model and shows an overlay to
+    /// step through them. Meant for development, snapshots are kept in memory.
+    pub fn debugger(mut self) -> Self
+    where
+        Model: Clone,
+        Msg: std::fmt::Debug,
+    {
+        self.debugger = Some(Debugger::new());
+        self
+    }
+
+    /// Starts from the model saved by `persistence` when there is one and saves it after every
+    /// update.
+    pub fn persist(mut self, persistence: Persistence) -> Self
+    where
+        Model: Serialize + DeserializeOwned,
+    {
+        let persistence = Rc::new(persistence);
+        self.persistence = Some(PersistenceHooks {
+            restore: Box::new({
+                let persistence = persistence.clone();
+                move || {
+                    async move {
+                        persistence.restore().await.unwrap_or_else(|message| {
+                            tracing::error!(?message, "restoring model failed, starting over");
+                            None
+                        })
+                    }
+                    .boxed_local()
+                }
+            }),
+            save: Box::new(move |model, executor| persistence.save(model, executor)),
+        });
+        self
+    }
+
+    /// Mounts the program into the first element matching `selector`.
+    pub fn mount(self, selector: &str) -> Result<ProgramHandle> {
+        crate::DOCUMENT
+            .with(|document| document.query_selector(selector))
+            .map_err(|e| eyre!("{e:?}"))
+            .wrap_err_with(|| format!("querying [{selector}]"))
+            .and_then(|root| root.ok_or_else(|| eyre!("no element matches [{selector}]")))
+            .map(|root| self.mount_to(root))
+    }
+
+    pub fn mount_to(self, root: impl Into<Element>) -> ProgramHandle {
+        let root = root.into();
+        let (stop, stopped) = oneshot::channel::<()>();
+        wasm_bindgen_futures::spawn_local(async move {
+            let Self {
+                init,
+                update,
+                view,
+                subscriptions,
+                executor,
+                mut debugger,
+                persistence,
+            } = self;
+            let (restore, save) = persistence.map(|hooks| (hooks.restore, hooks.save)).unzip();
+            let mut runtime = Runtime::new(root);
+            let (rx, communicator) = Communicator::create();
+            let (debug_rx, debug_communicator) = Communicator::<DebugMessage>::create();
+            let mut overlay = debugger.as_ref().and_then(|_| match overlay_runtime() {
+                Ok(overlay) => Some(overlay),
+                Err(message) => {
+                    tracing::error!(?message, "mounting debugger overlay failed");
+                    None
+                }
+            });
+            let send = {
+                let communicator = communicator.clone();
+                move |message: Msg| communicator.send(message)
+            };
+            let handle = |model: &mut Model,
+                          debugger: &mut Option<Debugger<Model, Msg>>,
+                          event: ProgramEvent<Msg>| match event {
+                ProgramEvent::App(message) => {
+                    let description = debugger
+                        .as_ref()
+                        .map(|debugger| debugger.describe(&message));
+                    update(model, message).run(&*executor, send.clone());
+                    if let Some(save) = save.as_ref() {
+                        save(model, &*executor);
+                    }
+                    if let Some((debugger, description)) = debugger.as_mut().zip(description) {
+                        debugger.record(description, model);
+                    }
+                }
+                ProgramEvent::Debug(message) => {
+                    if let Some(debugger) = debugger.as_mut() {
+                        debugger.handle(message);
+                    }
+                }
+            };
+            let mut running = RunningSubscriptions::default();
+            let mut refresh =
+                |runtime: &mut Runtime, model: &Model, debugger: &Option<Debugger<Model, Msg>>| {
+                    let viewed = debugger
+                        .as_ref()
+                        .map_or(model, |debugger| debugger.viewed(model));
+                    let app = runtime.build(|| view(viewed, communicator.clone()).build());
+                    rebuild(runtime, app);
+                    running.sync(subscriptions(model), &executor, send.clone());
+                    if let Some((overlay, debugger)) = overlay.as_mut().zip(debugger.as_ref()) {
+                        let debugger = overlay
+                            .build(|| debugger.overlay().render(&debug_communicator).build());
+                        rebuild(overlay, debugger);
+                    }
+                };
+
+            let mut model = match restore {
+                Some(restore) => restore().await.unwrap_or_else(init),
+                None => init(),
+            };
+            if let Some(debugger) = debugger.as_mut() {
+                debugger.record("init".to_string(), &model);
+            }
+            refresh(&mut runtime, &model, &debugger);
+            let mut messages = futures::stream::select(
+                rx.map(ProgramEvent::App),
+                debug_rx.map(ProgramEvent::Debug),
+            );
+            let mut stopped = stopped;
+            loop {
+                match futures::future::select(messages.next(), &mut stopped).await {
+                    Either::Left((Some(event), _)) => {
+                        handle(&mut model, &mut debugger, event);
+                        refresh(&mut runtime, &model, &debugger);
+                    }
+                    Either::Left((None, _)) => {
+                        tracing::debug!("all message sources finished");
+                        break;
+                    }
+                    // handle was dropped without stopping the program
+                    Either::Right((Err(oneshot::Canceled), _)) => {
+                        while let Some(event) = messages.next().await {
+                            handle(&mut model, &mut debugger, event);
+                            refresh(&mut runtime, &model, &debugger);
+                        }
+                        break;
+                    }
+                    Either::Right((Ok(()), _)) => {
+                        tracing::debug!("program stopped");
+                        communicator.close();
+                        debug_communicator.close();
+                        runtime.unmount();
+                        if let Some(overlay) = overlay.take() {
+                            let container = overlay.root_element().as_ref().clone();
+                            overlay.unmount();
+                            container.remove();
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+        ProgramHandle { stop }
+    }
+}
+
+enum ProgramEvent<Msg> {
+    App(Msg),
+    Debug(DebugMessage),
+}
+
+fn rebuild(runtime: &mut Runtime, view: ElementWithChildrenRecipe) {
+    if let Err(message) = runtime.dom_executor.rebuild(view) {
+        tracing::error!(?message, "rebuilding failed");
+    }
+}
+
+/// The debugger overlay lives in its own container so the app's DOM stays untouched.
+fn overlay_runtime() -> Result<Runtime> {
+    crate::DOCUMENT.with(|document| {
+        let container = document
+            .create_element("div")
+            .map_err(|e| eyre!("{e:?}"))
+            .wrap_err("creating overlay container")?;
+        document
+            .body()
+            .ok_or_else(|| eyre!("no body"))?
+            .append_child(&container)
+            .map_err(|e| eyre!("{e:?}"))
+            .wrap_err("appending overlay container")?;
+        Ok(Runtime::new(container))
+    })
+}
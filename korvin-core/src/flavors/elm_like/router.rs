@@ -0,0 +1,397 @@
+use super::{cmd::Cmd, sub::Sub};
+use eyre::{eyre, Result};
+use std::{collections::BTreeMap, fmt::Display, rc::Rc, str::FromStr};
+use wasm_bindgen::JsCast;
+use web_sys::MouseEvent;
+
+/// Dispatched on the window after [`push`] and [`replace`], `pushState` doesn't fire `popstate`.
+const URL_CHANGED: &str = "korvin-urlchange";
+
+/// A path split into segments plus the query string, both percent-decoded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Url {
+    pub segments: Vec<String>,
+    pub query: BTreeMap<String, String>,
+}
+
+impl Url {
+    /// Parses everything after the origin, e.g. `/users/7?tab=posts#bio`.
+    pub fn parse(url: &str) -> Self {
+        let url = url.split('#').next().unwrap_or_default();
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        Self {
+            segments: path
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| decode(segment, false))
+                .collect(),
+            query: query
+                .split('&')
+                .filter(|pair| !pair.is_empty())
+                .map(|pair| {
+                    let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                    (decode(key, true), decode(value, true))
+                })
+                .collect(),
+        }
+    }
+
+    pub fn path<S: Display>(segments: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            segments: segments
+                .into_iter()
+                .map(|segment| segment.to_string())
+                .collect(),
+            query: Default::default(),
+        }
+    }
+
+    pub fn query(mut self, key: impl Display, value: impl Display) -> Self {
+        self.query.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// The url the browser is currently at.
+    pub fn current() -> Result<Self> {
+        let location = web_sys::window()
+            .ok_or_else(|| eyre!("no window"))?
+            .location();
+        let path = location.pathname().map_err(|e| eyre!("{e:?}"))?;
+        let search = location.search().map_err(|e| eyre!("{e:?}"))?;
+        Ok(Self::parse(&format!("{path}{search}")))
+    }
+}
+
+impl Display for Url {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.segments.is_empty() {
+            write!(f, "/")?;
+        }
+        self.segments
+            .iter()
+            .try_for_each(|segment| write!(f, "/{}", encode(segment)))?;
+        self.query
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, (key, value))| {
+                let separator = if index == 0 { '?' } else { '&' };
+                write!(f, "{separator}{}={}", encode(key), encode(value))
+            })
+    }
+}
+
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// `+` only means a space in query strings
+fn decode(value: &str, plus_is_space: bool) -> String {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut input = value.bytes();
+    while let Some(byte) = input.next() {
+        match byte {
+            b'+' if plus_is_space => bytes.push(b' '),
+            b'%' => {
+                let hex = input.clone().take(2).collect::<Vec<_>>();
+                match std::str::from_utf8(&hex)
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(decoded) if hex.len() == 2 => {
+                        bytes.push(decoded);
+                        input.nth(1);
+                    }
+                    _ => bytes.push(byte),
+                }
+            }
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Values captured by `:name` and `*name` segments of the matched pattern, plus the query.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    captured: BTreeMap<String, String>,
+    rest: Vec<String>,
+    query: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.captured.get(name).map(String::as_str)
+    }
+
+    /// `None` when missing or not parseable.
+    pub fn parse<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get(name).and_then(|value| value.parse().ok())
+    }
+
+    /// Segments matched by a trailing `*name`.
+    pub fn rest(&self) -> &[String] {
+        &self.rest
+    }
+
+    pub fn query(&self, key: &str) -> Option<&str> {
+        self.query.get(key).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Capture(String),
+    Rest(String),
+}
+
+fn parse_pattern(pattern: &str) -> Vec<Segment> {
+    pattern
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match segment.chars().next() {
+            Some(':') => Segment::Capture(segment[1..].to_string()),
+            Some('*') => Segment::Rest(segment[1..].to_string()),
+            _ => Segment::Literal(segment.to_string()),
+        })
+        .collect()
+}
+
+fn matches(pattern: &[Segment], url: &Url) -> Option<Params> {
+    let mut captured = BTreeMap::new();
+    let mut segments = url.segments.iter();
+    for (index, expected) in pattern.iter().enumerate() {
+        match expected {
+            Segment::Rest(name) => {
+                let rest = url.segments[index..].to_vec();
+                if !name.is_empty() {
+                    captured.insert(name.clone(), rest.join("/"));
+                }
+                return Some(Params {
+                    captured,
+                    rest,
+                    query: url.query.clone(),
+                });
+            }
+            Segment::Literal(literal) => {
+                (segments.next()? == literal).then_some(())?;
+            }
+            Segment::Capture(name) => {
+                captured.insert(name.clone(), segments.next()?.clone());
+            }
+        }
+    }
+    segments.next().is_none().then(|| Params {
+        captured,
+        rest: Default::default(),
+        query: url.query.clone(),
+    })
+}
+
+type RouteFn<R> = Rc<dyn Fn(&Params) -> Option<R>>;
+
+/// Maps urls to a route type, first matching pattern wins.
+///
+/// ```ignore
+/// RouteTable::new(|_| Route::NotFound)
+///     .route("/", |_| Some(Route::Home))
+///     .route("/users/:id", |params| Some(Route::User(params.parse("id")?)))
+///     .nest("/admin", admin_routes(), Route::Admin)
+/// ```
+pub struct RouteTable<R> {
+    routes: Vec<(Vec<Segment>, RouteFn<R>)>,
+    fallback: Rc<dyn Fn(&Url) -> R>,
+}
+
+impl<R> Clone for RouteTable<R> {
+    fn clone(&self) -> Self {
+        Self {
+            routes: self.routes.clone(),
+            fallback: self.fallback.clone(),
+        }
+    }
+}
+
+impl<R: 'static> RouteTable<R> {
+    /// `fallback` is used when nothing matches, e.g. for a 404 page.
+    pub fn new(fallback: impl Fn(&Url) -> R + 'static) -> Self {
+        Self {
+            routes: Default::default(),
+            fallback: Rc::new(fallback),
+        }
+    }
+
+    /// `:name` segments capture one segment, a trailing `*name` captures the rest of the path.
+    /// Returning `None` from `route` (e.g. when a capture doesn't parse) tries the next pattern.
+    pub fn route(mut self, pattern: &str, route: impl Fn(&Params) -> Option<R> + 'static) -> Self {
+        self.routes.push((parse_pattern(pattern), Rc::new(route)));
+        self
+    }
+
+    /// Mounts `table` under `prefix`. Paths under the prefix that `table` doesn't know end up in
+    /// its own fallback.
+    pub fn nest<C: 'static>(
+        mut self,
+        prefix: &str,
+        table: RouteTable<C>,
+        wrap: impl Fn(C) -> R + 'static,
+    ) -> Self {
+        let prefix = parse_pattern(prefix);
+        let wrap = Rc::new(wrap);
+        let RouteTable { routes, fallback } = table;
+        routes.into_iter().for_each(|(child, route)| {
+            let wrap = wrap.clone();
+            self.routes.push((
+                prefix.iter().cloned().chain(child).collect(),
+                Rc::new(move |params| route(params).map(|route| wrap(route))),
+            ));
+        });
+        self.routes.push((
+            prefix
+                .into_iter()
+                .chain([Segment::Rest(Default::default())])
+                .collect(),
+            Rc::new(move |params| {
+                let url = Url {
+                    segments: params.rest.clone(),
+                    query: params.query.clone(),
+                };
+                Some(wrap(fallback(&url)))
+            }),
+        ));
+        self
+    }
+
+    pub fn parse(&self, url: &str) -> R {
+        self.resolve(&Url::parse(url))
+    }
+
+    pub fn resolve(&self, url: &Url) -> R {
+        self.routes
+            .iter()
+            .find_map(|(pattern, route)| matches(pattern, url).and_then(|params| route(&params)))
+            .unwrap_or_else(|| (self.fallback)(url))
+    }
+
+    /// The route the browser is currently at.
+    pub fn current(&self) -> Result<R> {
+        Url::current().map(|url| self.resolve(&url))
+    }
+
+    /// Delivers the route whenever the url changes, by the back/forward buttons, [`push`],
+    /// [`replace`] or clicks on internal links (which get intercepted instead of reloading).
+    pub fn changes<M: 'static>(&self, message: impl Fn(R) -> M + 'static) -> Sub<M> {
+        let table = self.clone();
+        let message = Rc::new(message);
+        let changes = |event: &'static str| {
+            let table = table.clone();
+            let message = message.clone();
+            Sub::builtin(("url_changes", event), move || {
+                super::sub::window_events(event, move |_: web_sys::Event| match table.current() {
+                    Ok(route) => Some(message(route)),
+                    Err(message) => {
+                        tracing::error!(?message, "reading current url");
+                        None
+                    }
+                })
+            })
+        };
+        Sub::batch([
+            changes("popstate"),
+            changes(URL_CHANGED),
+            Sub::builtin("intercept_links", || {
+                super::sub::window_events("click", |event: MouseEvent| {
+                    intercept_link(event);
+                    None
+                })
+            }),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Navigation {
+    Push,
+    Replace,
+}
+
+fn navigate(url: &str, navigation: Navigation) -> Result<()> {
+    let window = web_sys::window().ok_or_else(|| eyre!("no window"))?;
+    let history = window.history().map_err(|e| eyre!("{e:?}"))?;
+    match navigation {
+        Navigation::Push => {
+            history.push_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(url))
+        }
+        Navigation::Replace => {
+            history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(url))
+        }
+    }
+    .and_then(|()| web_sys::Event::new(URL_CHANGED))
+    .and_then(|event| window.dispatch_event(&event))
+    .map(|_| ())
+    .map_err(|e| eyre!("{e:?}"))
+}
+
+fn navigation_cmd<M: 'static>(url: String, navigation: Navigation) -> Cmd<M> {
+    Cmd::effect(move || {
+        if let Err(message) = navigate(&url, navigation) {
+            tracing::error!(?message, %url, ?navigation, "navigation failed");
+        }
+    })
+}
+
+/// Adds a history entry for `url`.
+pub fn push<M: 'static>(url: impl Display) -> Cmd<M> {
+    navigation_cmd(url.to_string(), Navigation::Push)
+}
+
+/// Replaces the current history entry with `url`.
+pub fn replace<M: 'static>(url: impl Display) -> Cmd<M> {
+    navigation_cmd(url.to_string(), Navigation::Replace)
+}
+
+/// Plain left clicks on `<a href="/...">` navigate through the history instead of reloading.
+fn intercept_link(event: MouseEvent) {
+    if event.default_prevented()
+        || event.button() != 0
+        || event.ctrl_key()
+        || event.meta_key()
+        || event.shift_key()
+        || event.alt_key()
+    {
+        return;
+    }
+    let Some(anchor) = event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        .and_then(|target| target.closest("a[href]").ok().flatten())
+    else {
+        return;
+    };
+    if anchor.has_attribute("download")
+        || anchor
+            .get_attribute("target")
+            .is_some_and(|target| target != "_self")
+    {
+        return;
+    }
+    let Some(href) = anchor.get_attribute("href") else {
+        return;
+    };
+    // only same-origin, absolute paths
+    if !href.starts_with('/') || href.starts_with("//") {
+        return;
+    }
+    event.prevent_default();
+    if let Err(message) = navigate(&href, Navigation::Push) {
+        tracing::error!(?message, %href, "following link failed");
+    }
+}
@@ -0,0 +1,354 @@
+use super::cmd::Executor;
+use crate::element_builder::calculate_hash;
+use futures::{
+    channel::mpsc::{self, UnboundedReceiver},
+    future::{AbortHandle, Abortable},
+    stream::LocalBoxStream,
+    FutureExt, Stream, StreamExt,
+};
+use std::{
+    any::TypeId,
+    collections::BTreeMap,
+    hash::Hash,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{EventTarget, KeyboardEvent, MediaQueryListEvent};
+
+/// Receives the executor the subscription runs on.
+type StartFn<M> = Box<dyn FnOnce(Rc<dyn Executor>) -> LocalBoxStream<'static, M>>;
+
+pub(crate) struct Subscription<M> {
+    pub(crate) id: u64,
+    pub(crate) start: StartFn<M>,
+}
+
+/// Message sources the [`super::program::Program`] listens to besides the views.
+///
+/// `subscriptions` is called after every update. Subscriptions are identified by their kind,
+/// parameters and [`Sub::key`]: new ones are started, the ones that are no longer returned are
+/// stopped and the rest keep running untouched.
+pub struct Sub<M> {
+    pub(crate) subscriptions: Vec<Subscription<M>>,
+}
+
+impl<M: 'static> Default for Sub<M> {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl<M: 'static> Sub<M> {
+    pub fn none() -> Self {
+        Self {
+            subscriptions: Default::default(),
+        }
+    }
+
+    /// `start` is only called when no subscription with the same key is running.
+    pub fn stream<S>(key: impl Hash, start: impl FnOnce() -> S + 'static) -> Self
+    where
+        S: Stream<Item = M> + 'static,
+    {
+        Self {
+            subscriptions: vec![Subscription {
+                id: calculate_hash(&("stream", calculate_hash(&key))),
+                start: Box::new(move |_| start().boxed_local()),
+            }],
+        }
+    }
+
+    pub fn batch(subs: impl IntoIterator<Item = Self>) -> Self {
+        Self {
+            subscriptions: subs.into_iter().flat_map(|sub| sub.subscriptions).collect(),
+        }
+    }
+
+    /// Distinguishes otherwise identical subscriptions, e.g. two key listeners.
+    pub fn key(self, key: impl Hash) -> Self {
+        let key = calculate_hash(&key);
+        Self {
+            subscriptions: self
+                .subscriptions
+                .into_iter()
+                .map(|Subscription { id, start }| Subscription {
+                    id: calculate_hash(&(id, key)),
+                    start,
+                })
+                .collect(),
+        }
+    }
+
+    /// The mapping function is part of the id, so the same subscription mapped by two children
+    /// runs twice. Mapping with the same closure but different captures (e.g. a child's index)
+    /// doesn't tell them apart, [`Sub::key`] them instead.
+    pub fn map<N: 'static, F>(self, map: F) -> Sub<N>
+    where
+        F: Fn(M) -> N + Clone + 'static,
+    {
+        let mapped_by = TypeId::of::<F>();
+        Sub {
+            subscriptions: self
+                .subscriptions
+                .into_iter()
+                .map(|Subscription { id, start }| {
+                    let map = map.clone();
+                    Subscription {
+                        id: calculate_hash(&(id, mapped_by)),
+                        start: Box::new(move |executor| start(executor).map(map).boxed_local()),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    pub(super) fn builtin(
+        id: impl Hash,
+        start: impl FnOnce() -> LocalBoxStream<'static, M> + 'static,
+    ) -> Self {
+        Self::on_executor(id, move |_| start())
+    }
+
+    fn on_executor(
+        id: impl Hash,
+        start: impl FnOnce(Rc<dyn Executor>) -> LocalBoxStream<'static, M> + 'static,
+    ) -> Self {
+        Self {
+            subscriptions: vec![Subscription {
+                id: calculate_hash(&id),
+                start: Box::new(start),
+            }],
+        }
+    }
+
+    /// Sleeps on the program's [`Executor`].
+    pub fn interval(every: Duration, message: impl Fn() -> M + 'static) -> Self {
+        Self::on_executor(("interval", every), move |executor| {
+            futures::stream::repeat(())
+                .then(move |()| executor.sleep(every))
+                .map(move |()| message())
+                .boxed_local()
+        })
+    }
+
+    /// Fires before every repaint with the frame timestamp in milliseconds.
+    pub fn animation_frames(message: impl Fn(f64) -> M + 'static) -> Self {
+        Self::builtin("animation_frames", move || {
+            futures::stream::unfold((), |()| async {
+                animation_frame().await.map(|timestamp| (timestamp, ()))
+            })
+            .map(message)
+            .boxed_local()
+        })
+    }
+
+    /// Inner width and height of the window, whenever it's resized.
+    pub fn resize(message: impl Fn(f64, f64) -> M + 'static) -> Self {
+        Self::builtin("resize", move || {
+            window_events("resize", move |_: web_sys::Event| {
+                let window = web_sys::window()?;
+                let width = window.inner_width().ok()?.as_f64()?;
+                let height = window.inner_height().ok()?.as_f64()?;
+                Some(message(width, height))
+            })
+        })
+    }
+
+    pub fn key_down(message: impl Fn(KeyboardEvent) -> Option<M> + 'static) -> Self {
+        Self::builtin("keydown", move || window_events("keydown", message))
+    }
+
+    pub fn key_up(message: impl Fn(KeyboardEvent) -> Option<M> + 'static) -> Self {
+        Self::builtin("keyup", move || window_events("keyup", message))
+    }
+
+    /// Whether the media query matches, once right away and then on every change.
+    pub fn media_query(query: &str, message: impl Fn(bool) -> M + 'static) -> Self {
+        let query = query.to_string();
+        Self::builtin(("media_query", query.clone()), move || {
+            let list = web_sys::window().and_then(|window| match window.match_media(&query) {
+                Ok(list) => list,
+                Err(message) => {
+                    tracing::error!(?message, %query, "bad media query");
+                    None
+                }
+            });
+            let Some(list) = list else {
+                return futures::stream::empty().boxed_local();
+            };
+            let matches = list.matches();
+            let changes = events(list.into(), "change", |event: MediaQueryListEvent| {
+                Some(event.matches())
+            });
+            futures::stream::once(async move { matches })
+                .chain(changes)
+                .map(message)
+                .boxed_local()
+        })
+    }
+
+    /// `true` when the page becomes visible, `false` when it gets hidden.
+    pub fn visibility(message: impl Fn(bool) -> M + 'static) -> Self {
+        Self::builtin("visibility", move || {
+            let document = crate::DOCUMENT.with(|document| document.clone());
+            events(
+                document.clone().into(),
+                "visibilitychange",
+                move |_: web_sys::Event| Some(message(!document.hidden())),
+            )
+        })
+    }
+}
+
+/// Subscriptions that were started, by id. Dropping one stops it. The
+/// [`super::program::Program`] syncs it with [`Sub`]s after every update.
+#[derive(Default)]
+pub struct RunningSubscriptions {
+    running: BTreeMap<u64, AbortHandle>,
+}
+
+impl RunningSubscriptions {
+    pub fn sync<Msg: 'static>(
+        &mut self,
+        sub: Sub<Msg>,
+        executor: &Rc<dyn Executor>,
+        send: impl Fn(Msg) + Clone + 'static,
+    ) {
+        let mut previous = std::mem::take(&mut self.running);
+        for Subscription { id, start } in sub.subscriptions {
+            if let Some(running) = previous.remove(&id) {
+                self.running.insert(id, running);
+                continue;
+            }
+            if self.running.contains_key(&id) {
+                tracing::warn!(
+                    id,
+                    "duplicate subscription, use Sub::key to tell them apart"
+                );
+                continue;
+            }
+            tracing::trace!(id, "starting subscription");
+            let (abort_handle, abort_registration) = AbortHandle::new_pair();
+            let send = send.clone();
+            let stream = start(executor.clone()).for_each(move |message| {
+                send(message);
+                futures::future::ready(())
+            });
+            executor.spawn(
+                Abortable::new(stream, abort_registration)
+                    .map(|_| ())
+                    .boxed_local(),
+            );
+            self.running.insert(id, abort_handle);
+        }
+        previous.into_iter().for_each(|(id, abort_handle)| {
+            tracing::trace!(id, "stopping subscription");
+            abort_handle.abort();
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.running.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.running.is_empty()
+    }
+}
+
+impl Drop for RunningSubscriptions {
+    fn drop(&mut self) {
+        self.running.values().for_each(AbortHandle::abort);
+    }
+}
+
+/// Messages from an event listener that gets removed when the stream is dropped.
+struct EventStream<M> {
+    messages: UnboundedReceiver<M>,
+    target: EventTarget,
+    name: &'static str,
+    closure: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl<M> Stream for EventStream<M> {
+    type Item = M;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.messages.poll_next_unpin(cx)
+    }
+}
+
+impl<M> Drop for EventStream<M> {
+    fn drop(&mut self) {
+        if let Err(message) = self
+            .target
+            .remove_event_listener_with_callback(self.name, self.closure.as_ref().unchecked_ref())
+        {
+            tracing::error!(?message, name = self.name, "removing subscription listener");
+        }
+    }
+}
+
+pub(super) fn events<E, M>(
+    target: EventTarget,
+    name: &'static str,
+    message: impl Fn(E) -> Option<M> + 'static,
+) -> LocalBoxStream<'static, M>
+where
+    E: JsCast + 'static,
+    M: 'static,
+{
+    let (tx, messages) = mpsc::unbounded();
+    let closure = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+        if let Some(message) = event.dyn_into::<E>().ok().and_then(&message) {
+            let _ = tx.unbounded_send(message);
+        }
+    });
+    if let Err(message) =
+        target.add_event_listener_with_callback(name, closure.as_ref().unchecked_ref())
+    {
+        tracing::error!(?message, name, "adding subscription listener");
+    }
+    EventStream {
+        messages,
+        target,
+        name,
+        closure,
+    }
+    .boxed_local()
+}
+
+pub(super) fn window_events<E, M>(
+    name: &'static str,
+    message: impl Fn(E) -> Option<M> + 'static,
+) -> LocalBoxStream<'static, M>
+where
+    E: JsCast + 'static,
+    M: 'static,
+{
+    match web_sys::window() {
+        Some(window) => events(window.into(), name, message),
+        None => {
+            tracing::error!(name, "no window to subscribe to");
+            futures::stream::empty().boxed_local()
+        }
+    }
+}
+
+/// `None` when the frame could not be requested
+async fn animation_frame() -> Option<f64> {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        let requested = web_sys::window()
+            .map(|window| window.request_animation_frame(&resolve))
+            .is_some_and(|requested| requested.is_ok());
+        if !requested {
+            let _ = resolve.call0(&wasm_bindgen::JsValue::NULL);
+        }
+    });
+    wasm_bindgen_futures::JsFuture::from(promise)
+        .map(|timestamp| timestamp.ok().and_then(|timestamp| timestamp.as_f64()))
+        .await
+}
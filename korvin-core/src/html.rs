@@ -0,0 +1,318 @@
+//! Typed element builders. Every element of the HTML standard has a constructor, and only the
+//! attributes it allows have methods, with enums for the enumerated ones. Everything lowers to
+//! a plain [`ElementBuilder`]:
+//!
+//! ```ignore
+//! use korvin_core::html::{self, InputType};
+//!
+//! html::label().r#for("slider").text("Duration")
+//! html::input()
+//!     .id("slider")
+//!     .r#type(InputType::Range)
+//!     .min(0)
+//!     .max(60)
+//! ```
+//!
+//! [`Element::attribute`] is still there for anything not covered, `data-*` and `aria-*`
+//! attributes have [`Element::data`] and [`Element::aria`].
+
+use crate::{
+    data::PropertyValue,
+    element_builder::{
+        value_cache::{IntoJsValue, IntoOptionalJsValue},
+        AsElementBuilder, ElementBuilder, ElementWithChildrenRecipe,
+    },
+    mutation::{
+        element::builder_mutation::modify::add_event_listener::{
+            by_event_kind, ElementAddEventListenerMutation,
+        },
+        traits::Perform,
+    },
+    style::Stylesheet,
+};
+use std::{future::Future, hash::Hash, marker::PhantomData};
+use wasm_bindgen::convert::{FromWasmAbi, RefFromWasmAbi};
+use web_sys::AbortSignal;
+
+pub use attributes::*;
+pub use elements::*;
+
+pub mod attributes;
+pub mod elements;
+
+/// Marker for an element kind, see [`elements::kind`].
+pub trait Kind {
+    const TAG: &'static str;
+}
+
+/// An element of kind `K`, use the constructors in [`elements`] to create one.
+pub struct Element<K> {
+    builder: ElementBuilder,
+    kind: PhantomData<K>,
+}
+
+impl<K: Kind> Default for Element<K> {
+    fn default() -> Self {
+        Self {
+            builder: ElementBuilder::builder(K::TAG),
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<K> Element<K> {
+    fn with(self, modify: impl FnOnce(ElementBuilder) -> ElementBuilder) -> Self {
+        Self {
+            builder: modify(self.builder),
+            kind: PhantomData,
+        }
+    }
+
+    fn set(self, name: &'static str, value: impl ToAttribute) -> Self {
+        self.with(|builder| value.set(builder, name))
+    }
+
+    pub fn text(self, text: impl IntoOptionalJsValue) -> Self {
+        self.with(|builder| builder.text(text))
+    }
+
+    pub fn input_value(self, value: impl IntoOptionalJsValue) -> Self {
+        self.with(|builder| builder.input_value(value))
+    }
+
+    pub fn checked(self, checked: bool) -> Self {
+        self.with(|builder| builder.checked(checked))
+    }
+
+    pub fn event<EventKind>(
+        self,
+        key: impl Hash,
+        name: impl IntoJsValue,
+        callback: impl Fn(EventKind) + 'static,
+    ) -> Self
+    where
+        EventKind: std::fmt::Debug + Sized + RefFromWasmAbi + FromWasmAbi + 'static,
+        by_event_kind::ElementAddEventListenerMutation<EventKind>:
+            Into<ElementAddEventListenerMutation> + Perform,
+    {
+        self.with(|builder| builder.event(key, name, callback))
+    }
+
+    pub fn child(self, child: impl Into<ElementBuilder>) -> Self {
+        self.with(|builder| builder.child(child))
+    }
+
+    pub fn children(self, children: impl IntoIterator<Item = impl Into<ElementBuilder>>) -> Self {
+        self.with(|builder| builder.children(children))
+    }
+
+    pub fn keyed_children<Item, Key: Hash, Child: Into<ElementBuilder>>(
+        self,
+        items: impl IntoIterator<Item = Item>,
+        key: impl Fn(&Item) -> Key,
+        render: impl Fn(Item) -> Child,
+    ) -> Self {
+        self.with(|builder| builder.keyed_children(items, key, render))
+    }
+
+    pub fn key(self, key: impl Hash) -> Self {
+        self.with(|builder| builder.key(key))
+    }
+
+    /// Untyped escape hatch for attributes without a method.
+    pub fn attribute(self, attribute: impl IntoJsValue, value: impl IntoOptionalJsValue) -> Self {
+        self.with(|builder| builder.attribute(attribute, value))
+    }
+
+    pub fn property(self, property: impl IntoJsValue, value: impl Into<PropertyValue>) -> Self {
+        self.with(|builder| builder.property(property, value))
+    }
+
+    pub fn class(self, class: &str) -> Self {
+        self.with(|builder| builder.class(class))
+    }
+
+    pub fn class_if(self, class: &str, enabled: bool) -> Self {
+        self.with(|builder| builder.class_if(class, enabled))
+    }
+
+    pub fn style(self, property: impl IntoJsValue, value: impl IntoOptionalJsValue) -> Self {
+        self.with(|builder| builder.style(property, value))
+    }
+
+    pub fn stylesheet(self, stylesheet: &Stylesheet) -> Self {
+        self.with(|builder| builder.stylesheet(stylesheet))
+    }
+
+    pub fn template(self) -> Self {
+        self.with(|builder| builder.template())
+    }
+
+    /// `data-{name}`
+    pub fn data(self, name: &str, value: impl IntoOptionalJsValue) -> Self {
+        self.with(|builder| builder.attribute(format!("data-{name}"), value))
+    }
+
+    /// `aria-{name}`
+    pub fn aria(self, name: &str, value: impl IntoOptionalJsValue) -> Self {
+        self.with(|builder| builder.attribute(format!("aria-{name}"), value))
+    }
+}
+
+macro_rules! attribute_methods {
+    ($($(#[$meta:meta])* $method:ident $name:literal: $value:ty;)*) => {
+        $(
+            $(#[$meta])*
+            pub fn $method(self, value: $value) -> Self {
+                self.set($name, value)
+            }
+        )*
+    };
+}
+pub(crate) use attribute_methods;
+
+/// Global attributes, allowed on every element.
+impl<K> Element<K> {
+    attribute_methods! {
+        accesskey "accesskey": &str;
+        autocapitalize "autocapitalize": Autocapitalize;
+        autofocus "autofocus": bool;
+        contenteditable "contenteditable": ContentEditable;
+        dir "dir": Dir;
+        enterkeyhint "enterkeyhint": EnterKeyHint;
+        hidden "hidden": bool;
+        id "id": &str;
+        inert "inert": bool;
+        inputmode "inputmode": InputMode;
+        is "is": &str;
+        itemid "itemid": &str;
+        itemprop "itemprop": &str;
+        itemref "itemref": &str;
+        itemscope "itemscope": bool;
+        itemtype "itemtype": &str;
+        lang "lang": &str;
+        nonce "nonce": &str;
+        part "part": &str;
+        popover "popover": Popover;
+        role "role": &str;
+        slot "slot": &str;
+        tabindex "tabindex": i32;
+        title "title": &str;
+    }
+
+    pub fn draggable(self, draggable: bool) -> Self {
+        self.set("draggable", TrueFalse(draggable))
+    }
+
+    pub fn spellcheck(self, spellcheck: bool) -> Self {
+        self.set("spellcheck", TrueFalse(spellcheck))
+    }
+
+    pub fn translate(self, translate: bool) -> Self {
+        self.set("translate", TrueFalse(translate))
+    }
+}
+
+impl<K> From<Element<K>> for ElementBuilder {
+    fn from(element: Element<K>) -> Self {
+        element.builder
+    }
+}
+
+impl<K> AsElementBuilder for Element<K> {
+    fn into_builder(self) -> ElementBuilder {
+        self.builder
+    }
+
+    fn text(self, text: impl IntoOptionalJsValue) -> ElementBuilder {
+        self.builder.text(text)
+    }
+
+    fn input_value(self, value: impl IntoOptionalJsValue) -> ElementBuilder {
+        self.builder.input_value(value)
+    }
+
+    fn checked(self, checked: bool) -> ElementBuilder {
+        self.builder.checked(checked)
+    }
+
+    fn event<Key: Hash, EventKind>(
+        self,
+        key: Key,
+        name: impl IntoJsValue,
+        callback: impl Fn(EventKind) + 'static,
+    ) -> ElementBuilder
+    where
+        EventKind: std::fmt::Debug + Sized + RefFromWasmAbi + FromWasmAbi + 'static,
+        by_event_kind::ElementAddEventListenerMutation<EventKind>:
+            Into<ElementAddEventListenerMutation> + Perform,
+    {
+        self.builder.event(key, name, callback)
+    }
+
+    fn event_async<Key: Hash, EventKind, Fut>(
+        self,
+        key: Key,
+        name: impl IntoJsValue,
+        callback: impl Fn(EventKind, AbortSignal) -> Fut + 'static,
+    ) -> ElementBuilder
+    where
+        Fut: Future<Output = ()> + 'static,
+        EventKind: std::fmt::Debug + Sized + RefFromWasmAbi + FromWasmAbi + 'static,
+        by_event_kind::ElementAddEventListenerMutation<EventKind>:
+            Into<ElementAddEventListenerMutation> + Perform,
+    {
+        self.builder.event_async(key, name, callback)
+    }
+
+    fn child(self, child: impl Into<ElementBuilder>) -> ElementBuilder {
+        self.builder.child(child)
+    }
+
+    fn key(self, key: impl Hash) -> ElementBuilder {
+        self.builder.key(key)
+    }
+
+    fn children(
+        self,
+        children: impl IntoIterator<Item = impl Into<ElementBuilder>>,
+    ) -> ElementBuilder {
+        self.builder.children(children)
+    }
+
+    fn attribute(
+        self,
+        attribute: impl IntoJsValue,
+        value: impl IntoOptionalJsValue,
+    ) -> ElementBuilder {
+        self.builder.attribute(attribute, value)
+    }
+
+    fn property(
+        self,
+        property: impl IntoJsValue,
+        value: impl Into<PropertyValue>,
+    ) -> ElementBuilder {
+        self.builder.property(property, value)
+    }
+
+    fn class(self, class: &str) -> ElementBuilder {
+        self.builder.class(class)
+    }
+
+    fn style(self, property: impl IntoJsValue, value: impl IntoOptionalJsValue) -> ElementBuilder {
+        self.builder.style(property, value)
+    }
+
+    fn stylesheet(self, stylesheet: &Stylesheet) -> ElementBuilder {
+        self.builder.stylesheet(stylesheet)
+    }
+
+    fn template(self) -> ElementBuilder {
+        self.builder.template()
+    }
+
+    fn build(self) -> ElementWithChildrenRecipe {
+        self.builder.build()
+    }
+}
@@ -0,0 +1,303 @@
+//! Values of typed attributes. Enumerated attributes get an enum with the keywords the HTML
+//! standard allows, everything else is a string, a number or a boolean attribute.
+
+use crate::element_builder::{AsElementBuilder, ElementBuilder};
+use std::fmt::Display;
+
+/// Something that can be the value of a typed attribute.
+pub trait ToAttribute {
+    fn set(self, builder: ElementBuilder, name: &'static str) -> ElementBuilder;
+}
+
+impl ToAttribute for &str {
+    fn set(self, builder: ElementBuilder, name: &'static str) -> ElementBuilder {
+        builder.attribute(name, self)
+    }
+}
+
+/// Boolean attributes are present or absent, `false` removes one set earlier.
+impl ToAttribute for bool {
+    fn set(self, builder: ElementBuilder, name: &'static str) -> ElementBuilder {
+        match self {
+            true => builder.attribute(name, ""),
+            false => builder.attribute(name, None::<&str>),
+        }
+    }
+}
+
+/// `None` removes a value set earlier.
+impl<T: ToAttribute> ToAttribute for Option<T> {
+    fn set(self, builder: ElementBuilder, name: &'static str) -> ElementBuilder {
+        match self {
+            Some(value) => value.set(builder, name),
+            None => builder.attribute(name, None::<&str>),
+        }
+    }
+}
+
+/// Numeric attribute values, `min`, `max`, `width`, `colspan`...
+pub trait Number: ToAttribute + Display + Copy {}
+
+macro_rules! numbers {
+    ($($number:ty),* $(,)?) => {
+        $(
+            impl Number for $number {}
+
+            impl ToAttribute for $number {
+                fn set(self, builder: ElementBuilder, name: &'static str) -> ElementBuilder {
+                    builder.attribute(name, self)
+                }
+            }
+        )*
+    };
+}
+
+numbers!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+/// Attributes spelled out as `"true"`/`"false"` instead of being present or absent, like
+/// `draggable` and `spellcheck`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrueFalse(pub bool);
+
+impl From<bool> for TrueFalse {
+    fn from(value: bool) -> Self {
+        Self(value)
+    }
+}
+
+impl ToAttribute for TrueFalse {
+    fn set(self, builder: ElementBuilder, name: &'static str) -> ElementBuilder {
+        builder.attribute(
+            name,
+            match self.0 {
+                true => "true",
+                false => "false",
+            },
+        )
+    }
+}
+
+macro_rules! enumerated {
+    ($(
+        $(#[$meta:meta])*
+        $name:ident { $($variant:ident => $keyword:literal),* $(,)? }
+    )*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum $name {
+                $($variant),*
+            }
+
+            impl $name {
+                pub fn as_str(self) -> &'static str {
+                    match self {
+                        $(Self::$variant => $keyword),*
+                    }
+                }
+            }
+
+            impl ToAttribute for $name {
+                fn set(self, builder: ElementBuilder, name: &'static str) -> ElementBuilder {
+                    builder.attribute(name, self.as_str())
+                }
+            }
+        )*
+    };
+}
+
+enumerated! {
+    /// `type` of an `<input>`
+    InputType {
+        Button => "button",
+        Checkbox => "checkbox",
+        Color => "color",
+        Date => "date",
+        DatetimeLocal => "datetime-local",
+        Email => "email",
+        File => "file",
+        Hidden => "hidden",
+        Image => "image",
+        Month => "month",
+        Number => "number",
+        Password => "password",
+        Radio => "radio",
+        Range => "range",
+        Reset => "reset",
+        Search => "search",
+        Submit => "submit",
+        Tel => "tel",
+        Text => "text",
+        Time => "time",
+        Url => "url",
+        Week => "week",
+    }
+    /// `type` of a `<button>`
+    ButtonType {
+        Submit => "submit",
+        Reset => "reset",
+        Button => "button",
+    }
+    /// `type` of an `<ol>`
+    ListType {
+        Decimal => "1",
+        LowerAlpha => "a",
+        UpperAlpha => "A",
+        LowerRoman => "i",
+        UpperRoman => "I",
+    }
+    /// `method` of a `<form>`, `formmethod` of its buttons
+    FormMethod {
+        Get => "get",
+        Post => "post",
+        Dialog => "dialog",
+    }
+    /// `enctype` of a `<form>`, `formenctype` of its buttons
+    FormEnctype {
+        UrlEncoded => "application/x-www-form-urlencoded",
+        Multipart => "multipart/form-data",
+        Plain => "text/plain",
+    }
+    /// Browsing context of links and forms
+    Target {
+        Current => "_self",
+        Blank => "_blank",
+        Parent => "_parent",
+        Top => "_top",
+    }
+    Dir {
+        Ltr => "ltr",
+        Rtl => "rtl",
+        Auto => "auto",
+    }
+    /// Only the on/off switch, field names like `"email"` go through `Element::attribute`
+    Autocomplete {
+        On => "on",
+        Off => "off",
+    }
+    Autocapitalize {
+        None => "none",
+        Sentences => "sentences",
+        Words => "words",
+        Characters => "characters",
+    }
+    InputMode {
+        None => "none",
+        Text => "text",
+        Decimal => "decimal",
+        Numeric => "numeric",
+        Tel => "tel",
+        Search => "search",
+        Email => "email",
+        Url => "url",
+    }
+    EnterKeyHint {
+        Enter => "enter",
+        Done => "done",
+        Go => "go",
+        Next => "next",
+        Previous => "previous",
+        Search => "search",
+        Send => "send",
+    }
+    ContentEditable {
+        True => "true",
+        False => "false",
+        PlaintextOnly => "plaintext-only",
+    }
+    Popover {
+        Auto => "auto",
+        Manual => "manual",
+    }
+    CrossOrigin {
+        Anonymous => "anonymous",
+        UseCredentials => "use-credentials",
+    }
+    ReferrerPolicy {
+        NoReferrer => "no-referrer",
+        NoReferrerWhenDowngrade => "no-referrer-when-downgrade",
+        SameOrigin => "same-origin",
+        Origin => "origin",
+        StrictOrigin => "strict-origin",
+        OriginWhenCrossOrigin => "origin-when-cross-origin",
+        StrictOriginWhenCrossOrigin => "strict-origin-when-cross-origin",
+        UnsafeUrl => "unsafe-url",
+    }
+    Loading {
+        Eager => "eager",
+        Lazy => "lazy",
+    }
+    Decoding {
+        Sync => "sync",
+        Async => "async",
+        Auto => "auto",
+    }
+    FetchPriority {
+        High => "high",
+        Low => "low",
+        Auto => "auto",
+    }
+    Preload {
+        None => "none",
+        Metadata => "metadata",
+        Auto => "auto",
+    }
+    /// `kind` of a `<track>`
+    TrackKind {
+        Subtitles => "subtitles",
+        Captions => "captions",
+        Descriptions => "descriptions",
+        Chapters => "chapters",
+        Metadata => "metadata",
+    }
+    /// `shape` of an `<area>`
+    Shape {
+        Circle => "circle",
+        Default => "default",
+        Poly => "poly",
+        Rect => "rect",
+    }
+    /// `scope` of a `<th>`
+    CellScope {
+        Row => "row",
+        Col => "col",
+        RowGroup => "rowgroup",
+        ColGroup => "colgroup",
+    }
+    /// `wrap` of a `<textarea>`
+    Wrap {
+        Soft => "soft",
+        Hard => "hard",
+    }
+    /// `as` of a preload `<link>`
+    LinkAs {
+        Audio => "audio",
+        Document => "document",
+        Embed => "embed",
+        Fetch => "fetch",
+        Font => "font",
+        Image => "image",
+        Object => "object",
+        Script => "script",
+        Style => "style",
+        Track => "track",
+        Video => "video",
+        Worker => "worker",
+    }
+    /// `http-equiv` of a `<meta>`
+    HttpEquiv {
+        ContentLanguage => "content-language",
+        ContentType => "content-type",
+        DefaultStyle => "default-style",
+        Refresh => "refresh",
+        SetCookie => "set-cookie",
+        XUaCompatible => "x-ua-compatible",
+        ContentSecurityPolicy => "content-security-policy",
+    }
+    /// `popovertargetaction` of a `<button>` or `<input>`
+    PopoverTargetAction {
+        Toggle => "toggle",
+        Show => "show",
+        Hide => "hide",
+    }
+}
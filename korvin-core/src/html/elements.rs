@@ -0,0 +1,451 @@
+//! One constructor per element of the HTML living standard, with the element specific
+//! attributes. Global attributes live on [`Element`] itself.
+
+use super::{attribute_methods, attributes::*, Element};
+
+macro_rules! elements {
+    ($(
+        $tag:literal $function:ident $kind:ident {
+            $($(#[$meta:meta])* $method:ident $name:literal: $value:ty;)*
+        }
+    )*) => {
+        /// Element kinds, the type parameter of [`Element`].
+        pub mod kind {
+            $(
+                #[doc = concat!("`<", $tag, ">`")]
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+                pub struct $kind;
+
+                impl crate::html::Kind for $kind {
+                    const TAG: &'static str = $tag;
+                }
+            )*
+        }
+
+        $(
+            #[doc = concat!("`<", $tag, ">`")]
+            pub fn $function() -> Element<kind::$kind> {
+                <Element<kind::$kind> as Default>::default()
+            }
+
+            impl Element<kind::$kind> {
+                attribute_methods! {
+                    $($(#[$meta])* $method $name: $value;)*
+                }
+            }
+        )*
+    };
+}
+
+elements! {
+    // document metadata
+    "html" html Html {}
+    "head" head Head {}
+    "title" title Title {}
+    "base" base Base {
+        href "href": &str;
+        target "target": Target;
+    }
+    "link" link Link {
+        r#as "as": LinkAs;
+        crossorigin "crossorigin": CrossOrigin;
+        disabled "disabled": bool;
+        fetchpriority "fetchpriority": FetchPriority;
+        href "href": &str;
+        hreflang "hreflang": &str;
+        imagesizes "imagesizes": &str;
+        imagesrcset "imagesrcset": &str;
+        integrity "integrity": &str;
+        media "media": &str;
+        referrerpolicy "referrerpolicy": ReferrerPolicy;
+        rel "rel": &str;
+        sizes "sizes": &str;
+        r#type "type": &str;
+    }
+    "meta" meta Meta {
+        charset "charset": &str;
+        content "content": &str;
+        http_equiv "http-equiv": HttpEquiv;
+        media "media": &str;
+        name "name": &str;
+    }
+    "style" style Style {
+        media "media": &str;
+    }
+
+    // sections
+    "body" body Body {}
+    "article" article Article {}
+    "section" section Section {}
+    "nav" nav Nav {}
+    "aside" aside Aside {}
+    "h1" h1 H1 {}
+    "h2" h2 H2 {}
+    "h3" h3 H3 {}
+    "h4" h4 H4 {}
+    "h5" h5 H5 {}
+    "h6" h6 H6 {}
+    "hgroup" hgroup Hgroup {}
+    "header" header Header {}
+    "footer" footer Footer {}
+    "address" address Address {}
+    "main" main Main {}
+    "search" search Search {}
+
+    // grouping content
+    "p" p P {}
+    "hr" hr Hr {}
+    "pre" pre Pre {}
+    "blockquote" blockquote Blockquote {
+        cite "cite": &str;
+    }
+    "ol" ol Ol {
+        reversed "reversed": bool;
+        start "start": impl Number;
+        r#type "type": ListType;
+    }
+    "ul" ul Ul {}
+    "menu" menu Menu {}
+    "li" li Li {
+        value "value": impl Number;
+    }
+    "dl" dl Dl {}
+    "dt" dt Dt {}
+    "dd" dd Dd {}
+    "figure" figure Figure {}
+    "figcaption" figcaption Figcaption {}
+    "div" div Div {}
+
+    // text-level semantics
+    "a" a A {
+        download "download": &str;
+        href "href": &str;
+        hreflang "hreflang": &str;
+        ping "ping": &str;
+        referrerpolicy "referrerpolicy": ReferrerPolicy;
+        rel "rel": &str;
+        target "target": Target;
+        r#type "type": &str;
+    }
+    "em" em Em {}
+    "strong" strong Strong {}
+    "small" small Small {}
+    "s" s S {}
+    "cite" cite Cite {}
+    "q" q Q {
+        cite "cite": &str;
+    }
+    "dfn" dfn Dfn {}
+    "abbr" abbr Abbr {}
+    "ruby" ruby Ruby {}
+    "rt" rt Rt {}
+    "rp" rp Rp {}
+    "data" data Data {
+        value "value": &str;
+    }
+    "time" time Time {
+        datetime "datetime": &str;
+    }
+    "code" code Code {}
+    "var" var Var {}
+    "samp" samp Samp {}
+    "kbd" kbd Kbd {}
+    "sub" sub Sub {}
+    "sup" sup Sup {}
+    "i" i I {}
+    "b" b B {}
+    "u" u U {}
+    "mark" mark Mark {}
+    "bdi" bdi Bdi {}
+    "bdo" bdo Bdo {}
+    "span" span Span {}
+    "br" br Br {}
+    "wbr" wbr Wbr {}
+
+    // edits
+    "ins" ins Ins {
+        cite "cite": &str;
+        datetime "datetime": &str;
+    }
+    "del" del Del {
+        cite "cite": &str;
+        datetime "datetime": &str;
+    }
+
+    // embedded content
+    "picture" picture Picture {}
+    "source" source Source {
+        height "height": impl Number;
+        media "media": &str;
+        sizes "sizes": &str;
+        src "src": &str;
+        srcset "srcset": &str;
+        r#type "type": &str;
+        width "width": impl Number;
+    }
+    "img" img Img {
+        alt "alt": &str;
+        crossorigin "crossorigin": CrossOrigin;
+        decoding "decoding": Decoding;
+        fetchpriority "fetchpriority": FetchPriority;
+        height "height": impl Number;
+        ismap "ismap": bool;
+        loading "loading": Loading;
+        referrerpolicy "referrerpolicy": ReferrerPolicy;
+        sizes "sizes": &str;
+        src "src": &str;
+        srcset "srcset": &str;
+        usemap "usemap": &str;
+        width "width": impl Number;
+    }
+    "iframe" iframe Iframe {
+        allow "allow": &str;
+        allowfullscreen "allowfullscreen": bool;
+        height "height": impl Number;
+        loading "loading": Loading;
+        name "name": &str;
+        referrerpolicy "referrerpolicy": ReferrerPolicy;
+        sandbox "sandbox": &str;
+        src "src": &str;
+        srcdoc "srcdoc": &str;
+        width "width": impl Number;
+    }
+    "embed" embed Embed {
+        height "height": impl Number;
+        src "src": &str;
+        r#type "type": &str;
+        width "width": impl Number;
+    }
+    "object" object Object {
+        /// `data`, the resource URL
+        data_url "data": &str;
+        form "form": &str;
+        height "height": impl Number;
+        name "name": &str;
+        r#type "type": &str;
+        width "width": impl Number;
+    }
+    "video" video Video {
+        autoplay "autoplay": bool;
+        controls "controls": bool;
+        crossorigin "crossorigin": CrossOrigin;
+        height "height": impl Number;
+        r#loop "loop": bool;
+        muted "muted": bool;
+        playsinline "playsinline": bool;
+        poster "poster": &str;
+        preload "preload": Preload;
+        src "src": &str;
+        width "width": impl Number;
+    }
+    "audio" audio Audio {
+        autoplay "autoplay": bool;
+        controls "controls": bool;
+        crossorigin "crossorigin": CrossOrigin;
+        r#loop "loop": bool;
+        muted "muted": bool;
+        preload "preload": Preload;
+        src "src": &str;
+    }
+    "track" track Track {
+        default "default": bool;
+        kind "kind": TrackKind;
+        label "label": &str;
+        src "src": &str;
+        srclang "srclang": &str;
+    }
+    "map" map Map {
+        name "name": &str;
+    }
+    "area" area Area {
+        alt "alt": &str;
+        coords "coords": &str;
+        download "download": &str;
+        href "href": &str;
+        ping "ping": &str;
+        referrerpolicy "referrerpolicy": ReferrerPolicy;
+        rel "rel": &str;
+        shape "shape": Shape;
+        target "target": Target;
+    }
+
+    // tabular data
+    "table" table Table {}
+    "caption" caption Caption {}
+    "colgroup" colgroup Colgroup {
+        span "span": impl Number;
+    }
+    "col" col Col {
+        span "span": impl Number;
+    }
+    "tbody" tbody Tbody {}
+    "thead" thead Thead {}
+    "tfoot" tfoot Tfoot {}
+    "tr" tr Tr {}
+    "td" td Td {
+        colspan "colspan": impl Number;
+        headers "headers": &str;
+        rowspan "rowspan": impl Number;
+    }
+    "th" th Th {
+        abbr "abbr": &str;
+        colspan "colspan": impl Number;
+        headers "headers": &str;
+        rowspan "rowspan": impl Number;
+        scope "scope": CellScope;
+    }
+
+    // forms
+    "form" form Form {
+        accept_charset "accept-charset": &str;
+        action "action": &str;
+        autocomplete "autocomplete": Autocomplete;
+        enctype "enctype": FormEnctype;
+        method "method": FormMethod;
+        name "name": &str;
+        novalidate "novalidate": bool;
+        rel "rel": &str;
+        target "target": Target;
+    }
+    "label" label Label {
+        r#for "for": &str;
+    }
+    "input" input Input {
+        accept "accept": &str;
+        alt "alt": &str;
+        autocomplete "autocomplete": &str;
+        dirname "dirname": &str;
+        disabled "disabled": bool;
+        form "form": &str;
+        formaction "formaction": &str;
+        formenctype "formenctype": FormEnctype;
+        formmethod "formmethod": FormMethod;
+        formnovalidate "formnovalidate": bool;
+        formtarget "formtarget": Target;
+        height "height": impl Number;
+        list "list": &str;
+        max "max": impl Number;
+        maxlength "maxlength": impl Number;
+        min "min": impl Number;
+        minlength "minlength": impl Number;
+        multiple "multiple": bool;
+        name "name": &str;
+        pattern "pattern": &str;
+        placeholder "placeholder": &str;
+        popovertarget "popovertarget": &str;
+        popovertargetaction "popovertargetaction": PopoverTargetAction;
+        readonly "readonly": bool;
+        required "required": bool;
+        size "size": impl Number;
+        src "src": &str;
+        step "step": impl Number;
+        r#type "type": InputType;
+        /// The initial value, use `input_value` to control it
+        value "value": &str;
+        width "width": impl Number;
+    }
+    "button" button Button {
+        disabled "disabled": bool;
+        form "form": &str;
+        formaction "formaction": &str;
+        formenctype "formenctype": FormEnctype;
+        formmethod "formmethod": FormMethod;
+        formnovalidate "formnovalidate": bool;
+        formtarget "formtarget": Target;
+        name "name": &str;
+        popovertarget "popovertarget": &str;
+        popovertargetaction "popovertargetaction": PopoverTargetAction;
+        r#type "type": ButtonType;
+        value "value": &str;
+    }
+    "select" select Select {
+        autocomplete "autocomplete": &str;
+        disabled "disabled": bool;
+        form "form": &str;
+        multiple "multiple": bool;
+        name "name": &str;
+        required "required": bool;
+        size "size": impl Number;
+    }
+    "datalist" datalist Datalist {}
+    "optgroup" optgroup Optgroup {
+        disabled "disabled": bool;
+        label "label": &str;
+    }
+    "option" option Option {
+        disabled "disabled": bool;
+        label "label": &str;
+        selected "selected": bool;
+        value "value": &str;
+    }
+    "textarea" textarea Textarea {
+        autocomplete "autocomplete": &str;
+        cols "cols": impl Number;
+        dirname "dirname": &str;
+        disabled "disabled": bool;
+        form "form": &str;
+        maxlength "maxlength": impl Number;
+        minlength "minlength": impl Number;
+        name "name": &str;
+        placeholder "placeholder": &str;
+        readonly "readonly": bool;
+        required "required": bool;
+        rows "rows": impl Number;
+        wrap "wrap": Wrap;
+    }
+    "output" output Output {
+        r#for "for": &str;
+        form "form": &str;
+        name "name": &str;
+    }
+    "progress" progress Progress {
+        max "max": impl Number;
+        value "value": impl Number;
+    }
+    "meter" meter Meter {
+        high "high": impl Number;
+        low "low": impl Number;
+        max "max": impl Number;
+        min "min": impl Number;
+        optimum "optimum": impl Number;
+        value "value": impl Number;
+    }
+    "fieldset" fieldset Fieldset {
+        disabled "disabled": bool;
+        form "form": &str;
+        name "name": &str;
+    }
+    "legend" legend Legend {}
+
+    // interactive elements
+    "details" details Details {
+        name "name": &str;
+        open "open": bool;
+    }
+    "summary" summary Summary {}
+    "dialog" dialog Dialog {
+        open "open": bool;
+    }
+
+    // scripting
+    "script" script Script {
+        r#async "async": bool;
+        crossorigin "crossorigin": CrossOrigin;
+        defer "defer": bool;
+        fetchpriority "fetchpriority": FetchPriority;
+        integrity "integrity": &str;
+        nomodule "nomodule": bool;
+        referrerpolicy "referrerpolicy": ReferrerPolicy;
+        src "src": &str;
+        r#type "type": &str;
+    }
+    "noscript" noscript Noscript {}
+    "template" template Template {}
+    "slot" slot Slot {
+        name "name": &str;
+    }
+    "canvas" canvas Canvas {
+        height "height": impl Number;
+        width "width": impl Number;
+    }
+}
@@ -1,23 +1,40 @@
 use data::ElementId;
-use dom_executor::DomExecutor;
+use dom_executor::{recycle::ElementPool, DomExecutor};
+use element_builder::value_cache::ValueCache;
 pub use js_sys;
+#[doc(hidden)]
+pub use korvin_macros::html_in as __html;
 use mutation::error::MutationError;
 use raw_operations::error::{DebugOf, RawOperationError};
+use stats::{LeakedClosure, RuntimeStats};
 use thiserror::Error;
 pub use web_sys;
 use web_sys::{Document, Element};
 pub mod flavors;
 
+/// [`element_builder::ElementBuilder`] trees with HTML-like syntax, see the `korvin-macros`
+/// crate for the syntax. Works wherever korvin_core is reachable, re-exports included.
+#[macro_export]
+macro_rules! html {
+    ($($tokens:tt)*) => {
+        $crate::__html! { $crate; $($tokens)* }
+    };
+}
+
 thread_local! {
     pub static DOCUMENT: web_sys::Document = crate::get_document().expect("document not present");
 }
 
+pub mod component;
 pub mod data;
 pub mod document_model;
 pub mod dom_executor;
 pub mod element_builder;
+pub mod html;
 pub mod mutation;
 pub mod raw_operations;
+pub mod stats;
+pub mod style;
 pub mod utils;
 
 #[derive(Error, Debug)]
@@ -55,6 +72,14 @@ pub enum RuntimeError {
     RuntimeCrashedOnPreviousRedraw,
     #[error("Reinserting old child resulted in an error.")]
     ReinsertingOldChild(#[source] RawOperationError),
+    #[error("Inserting a newly built child resulted in an error.")]
+    InsertingNewChild(#[source] RawOperationError),
+    #[error("Building from a template: {0}")]
+    Template(#[source] RawOperationError),
+    #[error(
+        "<{tag}> in a template has both text and children, setting the text would remove them"
+    )]
+    TemplateTextWithChildren { tag: String },
     #[error("Removing old child.")]
     RemovingElement(#[source] RawOperationError),
 }
@@ -85,4 +110,63 @@ impl Runtime {
             dom_executor: DomExecutor::new(ElementId::new(root_element.into())),
         }
     }
+
+    /// Enables leak checking: after every rebuild closures that outlived the element they were
+    /// attached to are reported with `tracing::error!`. Bookkeeping is per thread, so it affects
+    /// every runtime on it.
+    pub fn with_leak_check(self, enabled: bool) -> Self {
+        stats::set_leak_check(enabled);
+        self
+    }
+
+    /// Bounds the values cached between rebuilds, least recently used ones are dropped first.
+    pub fn with_value_cache_capacity(self, capacity: usize) -> Self {
+        *self.dom_executor.value_cache.borrow_mut() = ValueCache::with_capacity(capacity);
+        self
+    }
+
+    /// Keeps up to `capacity` removed elements of every tag and reuses them for new ones, so that
+    /// churning lists don't keep creating the same elements. Pooled elements are reset from the
+    /// mutations applied to them. Disabled (`0`) by default.
+    pub fn with_element_pool(mut self, capacity: usize) -> Self {
+        self.dom_executor.element_pool = ElementPool::with_capacity(capacity);
+        self
+    }
+
+    /// Runs `view` with this runtime's value cache, values its builders create are cached for
+    /// this runtime instead of the thread's default cache.
+    ///
+    /// ```ignore
+    /// let view = runtime.build(|| app(&model).build());
+    /// runtime.dom_executor.rebuild(view)?;
+    /// ```
+    pub fn build<R>(&self, view: impl FnOnce() -> R) -> R {
+        element_builder::value_cache::with_cache(&self.dom_executor.value_cache, view)
+    }
+
+    /// Numbers of this runtime only, the ones shared by every runtime on the thread are in
+    /// [`stats::thread`].
+    pub fn stats(&self) -> RuntimeStats {
+        let cached_values = self.dom_executor.value_cache.borrow().len();
+        stats::current(
+            cached_values,
+            self.dom_executor.element_pool.stats(),
+            self.dom_executor.templates.stats(),
+        )
+    }
+
+    /// Removes everything rendered by this runtime, leaving the root element itself in place.
+    pub fn unmount(mut self) {
+        if let Some(executed) = self.dom_executor.executed.take() {
+            executed.children.into_iter().for_each(|child| {
+                raw_operations::remove_element_in_place(child.element.create.log.element_id);
+            });
+        }
+    }
+
+    /// Closures still alive after their element was removed. Always empty unless
+    /// [`Runtime::with_leak_check`] was enabled.
+    pub fn leaked_closures(&self) -> Vec<LeakedClosure> {
+        stats::leaked_closures()
+    }
 }
@@ -20,14 +20,14 @@ impl_complex_mutation! {
     mutation = ElementCreateMutation,
     log = ElementCreateMutationLog,
     reverse = super::super::super::cleanup_mutation::marker::uncreate::Mutation,
-    fn perform(&self, parent: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
+    /// The element is created detached, the executor inserts it once it's fully built.
+    fn perform(&self, _parent: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
         let Self { kind } = self.clone();
         crate::DOCUMENT.with(|document| {
             raw_operations::create_element(document, kind.clone())
-                .and_then(|element| raw_operations::insert_element(element, parent.clone()))
                 .map_err(MutationError::ElementCreate)
-                .map(|inserted| {
-                    Self::Log { kind, element_id: inserted }
+                .map(|element_id| {
+                    Self::Log { kind, element_id }
                 })
         })
 
@@ -1,8 +1,13 @@
 use crate::impl_complex_mutation_wrapper;
+pub mod add_class;
 pub mod add_event_listener;
 pub mod set_attribute;
+pub mod set_checked;
 pub mod set_input_value;
+pub mod set_property;
+pub mod set_style;
 pub mod set_text;
+pub mod use_stylesheet;
 
 impl_complex_mutation_wrapper! {
     reverse = super::super::cleanup_mutation::modify::Mutation,
@@ -12,11 +17,21 @@ impl_complex_mutation_wrapper! {
         AddEventListener(add_event_listener::Mutation),
         SetText(set_text::Mutation),
         SetInputValue(set_input_value::Mutation),
+        SetChecked(set_checked::Mutation),
+        SetProperty(set_property::Mutation),
+        AddClass(add_class::Mutation),
+        SetStyle(set_style::Mutation),
+        UseStylesheet(use_stylesheet::Mutation),
     },
     enum ElementBuilderModifyMutationLog {
         SetAttribute(set_attribute::Log),
         AddEventListener(add_event_listener::Log),
         SetText(set_text::Log),
         SetInputValue(set_input_value::Log),
+        SetChecked(set_checked::Log),
+        SetProperty(set_property::Log),
+        AddClass(add_class::Log),
+        SetStyle(set_style::Log),
+        UseStylesheet(use_stylesheet::Log),
     }
 }
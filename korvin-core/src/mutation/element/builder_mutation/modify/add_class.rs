@@ -0,0 +1,33 @@
+use crate::{
+    data::AttributeValue, impl_complex_mutation, mutation::error::MutationError, raw_operations,
+};
+
+#[derive(Debug, PartialEq, Clone, Eq, Hash, PartialOrd)]
+pub struct ElementAddClassMutation {
+    pub class: AttributeValue,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementAddClassMutationLog {
+    pub class: AttributeValue,
+    /// the class was there before, so it's not korvin's to remove
+    pub was_present: bool,
+}
+
+impl_complex_mutation! {
+    mutation = ElementAddClassMutation,
+    log = ElementAddClassMutationLog,
+    reverse = super::super::super::cleanup_mutation::modify::remove_class::Mutation,
+    fn perform(&self, element: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
+        let Self { class } = self.clone();
+        raw_operations::add_class(element, &class)
+            .map_err(MutationError::ClassList)
+            .map(|was_present| {
+                Self::Log { class, was_present }
+            })
+    },
+    fn revert(&self) -> Self::Mutation {
+        let Self { class, was_present } = self.clone();
+        Self::Mutation { class, was_present }
+    }
+}
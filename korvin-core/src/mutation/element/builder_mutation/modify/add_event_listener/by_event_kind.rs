@@ -5,7 +5,9 @@ use crate::{
         error::MutationError,
     },
     raw_operations,
+    stats::{Counter, LiveToken},
 };
+use std::rc::Rc;
 
 pub type Mutation<E> = ElementAddEventListenerMutation<E>;
 pub type Log<E> = ElementAddEventListenerMutationLog<E>;
@@ -52,6 +54,7 @@ impl<E> Clone for ElementAddEventListenerMutationLog<E> {
     fn clone(&self) -> Self {
         Self {
             listener: self.listener.clone(),
+            registration: Rc::clone(&self.registration),
         }
     }
 }
@@ -62,6 +65,8 @@ where
     EventKind: 'static,
 {
     listener: EventListenerWrapper<EventKind>,
+    /// the listener stays registered for as long as the log is kept in a snapshot
+    registration: Rc<LiveToken>,
 }
 
 impl<EventKind: std::fmt::Debug> crate::mutation::traits::Perform
@@ -81,7 +86,10 @@ where
         let Self { listener } = self.clone();
         raw_operations::add_event_listener(element, listener)
             .map_err(MutationError::ElementAddEventListener)
-            .map(|listener| Self::Log { listener })
+            .map(|listener| Self::Log {
+                listener,
+                registration: Rc::new(LiveToken::new(Counter::ListenerRegistrations)),
+            })
     }
 }
 
@@ -94,7 +102,7 @@ where
         self.into().to_mutation_log()
     }
     fn revert(&self) -> Self::Mutation {
-        let Self { listener } = self.clone();
+        let Self { listener, .. } = self.clone();
         Self::Mutation { listener }
     }
 }
@@ -0,0 +1,30 @@
+use crate::{impl_complex_mutation, mutation::error::MutationError, raw_operations};
+
+#[derive(Debug, PartialEq, Clone, Eq, Hash, PartialOrd)]
+pub struct ElementSetCheckedMutation {
+    pub checked: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementSetCheckedMutationLog {
+    pub previous_checked: bool,
+}
+
+impl_complex_mutation! {
+    mutation = ElementSetCheckedMutation,
+    log = ElementSetCheckedMutationLog,
+    reverse = super::super::super::cleanup_mutation::modify::unset_checked::Mutation,
+    fn perform(&self, element: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
+        let Self { checked } = self.clone();
+        raw_operations::set_checked(element, checked).map_err(MutationError::SetChecked)
+            .map(|previous_checked| {
+                Self::Log { previous_checked }
+            })
+    },
+    fn revert(&self) -> Self::Mutation {
+        let Self { previous_checked } = self.clone();
+        Self::Mutation {
+            previous_checked
+        }
+    }
+}
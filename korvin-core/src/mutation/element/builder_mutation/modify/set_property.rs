@@ -0,0 +1,36 @@
+use crate::{
+    data::{PropertyName, PropertyValue},
+    impl_complex_mutation,
+    mutation::error::MutationError,
+    raw_operations,
+};
+
+#[derive(Debug, PartialEq, Clone, Eq, Hash, PartialOrd)]
+pub struct ElementSetPropertyMutation {
+    pub property: PropertyName,
+    pub value: PropertyValue,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementSetPropertyMutationLog {
+    pub property: PropertyName,
+    pub previous_value: PropertyValue,
+}
+
+impl_complex_mutation! {
+    mutation = ElementSetPropertyMutation,
+    log = ElementSetPropertyMutationLog,
+    reverse = super::super::super::cleanup_mutation::modify::unset_property::Mutation,
+    fn perform(&self, element: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
+        let Self { property, value } = self.clone();
+        raw_operations::set_property(element, &property, value.value())
+            .map_err(MutationError::SetProperty)
+            .map(|previous_value| {
+                Self::Log { property, previous_value: previous_value.into() }
+            })
+    },
+    fn revert(&self) -> Self::Mutation {
+        let Self { property, previous_value } = self.clone();
+        Self::Mutation { property, previous_value }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{
+    data::{AttributeName, AttributeValue},
+    impl_complex_mutation,
+    mutation::error::MutationError,
+    raw_operations,
+};
+
+#[derive(Debug, PartialEq, Clone, Eq, Hash, PartialOrd)]
+pub struct ElementSetStyleMutation {
+    pub property: AttributeName,
+    pub value: Option<AttributeValue>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementSetStyleMutationLog {
+    pub property: AttributeName,
+    pub previous_value: Option<AttributeValue>,
+}
+
+impl_complex_mutation! {
+    mutation = ElementSetStyleMutation,
+    log = ElementSetStyleMutationLog,
+    reverse = super::super::super::cleanup_mutation::modify::unset_style::Mutation,
+    fn perform(&self, element: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
+        let Self { property, value } = self.clone();
+        raw_operations::set_style(element, &property, value.as_ref())
+            .map_err(MutationError::SetStyle)
+            .map(|previous_value| {
+                Self::Log { property, previous_value }
+            })
+    },
+    fn revert(&self) -> Self::Mutation {
+        let Self { property, previous_value } = self.clone();
+        Self::Mutation { property, previous_value }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{data::AttributeValue, impl_complex_mutation, style::StylesheetGuard};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone, Eq, Hash, PartialOrd, Ord)]
+pub struct ElementUseStylesheetMutation {
+    pub scope: AttributeValue,
+    pub css: AttributeValue,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementUseStylesheetMutationLog {
+    pub scope: AttributeValue,
+    pub css: AttributeValue,
+    /// the stylesheet is removed from the document once the last guard is dropped, together with
+    /// the snapshot of the last element using it
+    pub guard: Rc<StylesheetGuard>,
+}
+
+impl_complex_mutation! {
+    mutation = ElementUseStylesheetMutation,
+    log = ElementUseStylesheetMutationLog,
+    reverse = super::super::super::cleanup_mutation::modify::release_stylesheet::Mutation,
+    fn perform(&self, _element: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
+        let Self { scope, css } = self.clone();
+        let guard = StylesheetGuard::acquire(&scope, &css);
+        Ok(Self::Log { scope, css, guard })
+    },
+    fn revert(&self) -> Self::Mutation {
+        let Self { scope, css, guard } = self.clone();
+        Self::Mutation { scope, css, guard }
+    }
+}
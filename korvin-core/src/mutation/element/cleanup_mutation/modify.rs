@@ -1,7 +1,12 @@
 use crate::impl_complex_mutation_wrapper;
+pub mod release_stylesheet;
+pub mod remove_class;
 pub mod remove_event_listener;
 pub mod unset_attribute;
+pub mod unset_checked;
 pub mod unset_input_value;
+pub mod unset_property;
+pub mod unset_style;
 pub mod unset_text;
 
 impl_complex_mutation_wrapper! {
@@ -11,11 +16,21 @@ impl_complex_mutation_wrapper! {
         RemoveEventListener(remove_event_listener::Mutation),
         UnsetText(unset_text::Mutation),
         UnsetInputValue(unset_input_value::Mutation),
+        UnsetChecked(unset_checked::Mutation),
+        UnsetProperty(unset_property::Mutation),
+        RemoveClass(remove_class::Mutation),
+        UnsetStyle(unset_style::Mutation),
+        ReleaseStylesheet(release_stylesheet::Mutation),
     },
     enum ElementCleanupModifyMutationLog {
         UnsetAttribute(unset_attribute::Log),
         RemoveEventListener(remove_event_listener::Log),
         UnsetText(unset_text::Log),
         UnsetInputValue(unset_input_value::Log),
+        UnsetChecked(unset_checked::Log),
+        UnsetProperty(unset_property::Log),
+        RemoveClass(remove_class::Log),
+        UnsetStyle(unset_style::Log),
+        ReleaseStylesheet(release_stylesheet::Log),
     }
 }
@@ -0,0 +1,30 @@
+use crate::{data::AttributeValue, impl_complex_mutation, style::StylesheetGuard};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementReleaseStylesheetMutation {
+    pub scope: AttributeValue,
+    pub css: AttributeValue,
+    pub guard: Rc<StylesheetGuard>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementReleaseStylesheetMutationLog {
+    pub scope: AttributeValue,
+    pub css: AttributeValue,
+}
+
+impl_complex_mutation! {
+    mutation = ElementReleaseStylesheetMutation,
+    log = ElementReleaseStylesheetMutationLog,
+    reverse = super::super::super::builder_mutation::modify::use_stylesheet::Mutation,
+    fn perform(&self, _root_element: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
+        // the guard is released when this mutation is dropped
+        let Self { scope, css, guard: _ } = self.clone();
+        Ok(Self::Log { scope, css })
+    },
+    fn revert(&self) -> Self::Mutation {
+        let Self { scope, css } = self.clone();
+        Self::Mutation { scope, css }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{
+    data::AttributeValue, impl_complex_mutation, mutation::error::MutationError, raw_operations,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementRemoveClassMutation {
+    pub class: AttributeValue,
+    pub was_present: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementRemoveClassMutationLog {
+    pub class: AttributeValue,
+}
+
+impl_complex_mutation! {
+    mutation = ElementRemoveClassMutation,
+    log = ElementRemoveClassMutationLog,
+    reverse = super::super::super::builder_mutation::modify::add_class::Mutation,
+    fn perform(&self, root_element: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
+        let Self { class, was_present } = self.clone();
+        match was_present {
+            true => Ok(Self::Log { class }),
+            false => raw_operations::remove_class(root_element, &class)
+                .map_err(MutationError::ClassList)
+                .map(|_| Self::Log { class }),
+        }
+    },
+    fn revert(&self) -> Self::Mutation {
+        let Self { class } = self.clone();
+        Self::Mutation { class }
+    }
+}
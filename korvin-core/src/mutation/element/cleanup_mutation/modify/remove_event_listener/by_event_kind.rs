@@ -74,8 +74,7 @@ where
     }
 }
 
-impl<EventKind> crate::mutation::traits::Revert
-    for ElementRemoveEventListenerMutationLog<EventKind>
+impl<EventKind> crate::mutation::traits::Revert for ElementRemoveEventListenerMutationLog<EventKind>
 where
     Self: Into<super::Log>,
 {
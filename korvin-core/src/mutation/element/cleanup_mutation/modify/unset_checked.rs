@@ -0,0 +1,27 @@
+use crate::{impl_complex_mutation, mutation::error::MutationError, raw_operations};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementUnsetCheckedMutation {
+    pub previous_checked: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementUnsetCheckedMutationLog {
+    pub previous_checked: bool,
+}
+
+impl_complex_mutation! {
+    mutation = ElementUnsetCheckedMutation,
+    log = ElementUnsetCheckedMutationLog,
+    reverse = super::super::super::builder_mutation::modify::set_checked::Mutation,
+    fn perform(&self, state: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
+        let Self { previous_checked } = self.clone();
+        let element = state;
+        let previous_checked = raw_operations::set_checked(element, previous_checked).map_err(MutationError::SetChecked)?;
+        Ok(Self::Log { previous_checked })
+    },
+    fn revert(&self) -> Self::Mutation {
+        let Self { previous_checked } = self.clone();
+        Self::Mutation { checked: previous_checked }
+    }
+}
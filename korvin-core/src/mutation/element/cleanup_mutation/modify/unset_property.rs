@@ -0,0 +1,36 @@
+use crate::{
+    data::{PropertyName, PropertyValue},
+    impl_complex_mutation,
+    mutation::error::MutationError,
+    raw_operations,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementUnsetPropertyMutation {
+    pub property: PropertyName,
+    pub previous_value: PropertyValue,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementUnsetPropertyMutationLog {
+    pub property: PropertyName,
+    pub previous_value: PropertyValue,
+}
+
+impl_complex_mutation! {
+    mutation = ElementUnsetPropertyMutation,
+    log = ElementUnsetPropertyMutationLog,
+    reverse = super::super::super::builder_mutation::modify::set_property::Mutation,
+    fn perform(&self, root_element: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
+        let Self { property, previous_value } = self.clone();
+        raw_operations::set_property(root_element, &property, previous_value.value())
+            .map_err(MutationError::SetProperty)
+            .map(|previous_value| {
+                Self::Log { property, previous_value: previous_value.into() }
+            })
+    },
+    fn revert(&self) -> Self::Mutation {
+        let Self { property, previous_value } = self.clone();
+        Self::Mutation { property, value: previous_value }
+    }
+}
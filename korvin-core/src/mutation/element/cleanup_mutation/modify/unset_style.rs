@@ -0,0 +1,36 @@
+use crate::{
+    data::{AttributeName, AttributeValue},
+    impl_complex_mutation,
+    mutation::error::MutationError,
+    raw_operations,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementUnsetStyleMutation {
+    pub property: AttributeName,
+    pub previous_value: Option<AttributeValue>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ElementUnsetStyleMutationLog {
+    pub property: AttributeName,
+    pub previous_value: Option<AttributeValue>,
+}
+
+impl_complex_mutation! {
+    mutation = ElementUnsetStyleMutation,
+    log = ElementUnsetStyleMutationLog,
+    reverse = super::super::super::builder_mutation::modify::set_style::Mutation,
+    fn perform(&self, root_element: crate::data::ElementId) -> crate::mutation::error::MutationResult<Self::Log> {
+        let Self { property, previous_value } = self.clone();
+        raw_operations::set_style(root_element, &property, previous_value.as_ref())
+            .map_err(MutationError::SetStyle)
+            .map(|previous_value| {
+                Self::Log { property, previous_value }
+            })
+    },
+    fn revert(&self) -> Self::Mutation {
+        let Self { property, previous_value } = self.clone();
+        Self::Mutation { property, value: previous_value }
+    }
+}
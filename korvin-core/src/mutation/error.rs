@@ -15,6 +15,12 @@ pub enum MutationError {
     SetAttribute(#[source] RawOperationError),
     #[error("Setting attribute: {0}")]
     UnsetAttribute(#[source] RawOperationError),
+    #[error("Changing class: {0}")]
+    ClassList(#[source] RawOperationError),
+    #[error("Setting style: {0}")]
+    SetStyle(#[source] RawOperationError),
+    #[error("Setting property: {0}")]
+    SetProperty(#[source] RawOperationError),
     #[error("Adding event listener: {0}")]
     ElementAddEventListener(#[source] RawOperationError),
     #[error("Removing event listener: {0}")]
@@ -23,8 +29,10 @@ pub enum MutationError {
     NoElementInBuilder,
     #[error("Document model went out of sync with the actual DOM: {0}")]
     DocumentModel(#[source] DocumentModelError),
-    #[error("Setting <input> node's .value failed.")]
+    #[error("Setting form control's value failed.")]
     SetInputValue(#[source] RawOperationError),
+    #[error("Setting <input> node's .checked failed.")]
+    SetChecked(#[source] RawOperationError),
 }
 
 pub type MutationResult<T> = std::result::Result<T, MutationError>;
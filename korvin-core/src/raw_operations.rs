@@ -1,8 +1,13 @@
 use self::error::{DebugOf, JsError, RawOperationError, RawOperationResult};
-use crate::data::{AttributeName, AttributeValue, ElementId, EventListenerWrapper, TagName};
+use crate::data::{
+    AttributeName, AttributeValue, ElementId, EventListenerWrapper, PropertyName, TagName,
+};
 use tracing::instrument;
-use wasm_bindgen::JsCast;
-use web_sys::{Document, HtmlInputElement, Node};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    Document, Element, HtmlElement, HtmlInputElement, HtmlOptionElement, HtmlSelectElement,
+    HtmlTemplateElement, HtmlTextAreaElement, Node, SvgElement,
+};
 
 pub mod attribute;
 pub mod error;
@@ -16,6 +21,12 @@ impl<T> Removed<T> {
     }
 }
 
+/// Tag, attribute, style property and event names repeat on every rebuild, interned they are
+/// only converted to JS strings once.
+fn name(name: &impl AsRef<str>) -> &str {
+    wasm_bindgen::intern(name.as_ref())
+}
+
 #[instrument(level = "trace", ret)]
 pub fn remove_element_in_place(element_id: ElementId) -> Removed<ElementId> {
     element_id.as_ref().remove();
@@ -91,10 +102,10 @@ pub fn replace_element(element: ElementId, with: ElementId) -> RawOperationResul
 #[instrument(level = "trace", ret, err)]
 pub fn create_element(document: &Document, kind: TagName) -> RawOperationResult<ElementId> {
     document
-        .create_element(kind.as_ref())
+        .create_element(name(&kind))
         .map_err(JsError::from)
         .map_err(|source| RawOperationError::CreatingElement { kind, source })
-        .map(ElementId::new)
+        .map(ElementId::created)
 }
 
 pub(crate) fn add_event_listener<EventKind>(
@@ -104,12 +115,15 @@ pub(crate) fn add_event_listener<EventKind>(
     element
         .as_ref()
         .add_event_listener_with_callback(
-            event_listener.name.as_ref(),
+            name(&event_listener.name),
             event_listener.closure.js_function(),
         )
         .map_err(JsError::from)
         .map_err(RawOperationError::AddEventListener)
-        .map(|_| event_listener)
+        .map(|_| {
+            crate::stats::register_listener(&element, &event_listener);
+            event_listener
+        })
 }
 
 pub(crate) fn remove_event_listener<EventKind>(
@@ -119,7 +133,7 @@ pub(crate) fn remove_event_listener<EventKind>(
     element
         .as_ref()
         .remove_event_listener_with_callback(
-            event_listener.name.as_ref(),
+            name(&event_listener.name),
             event_listener.closure.js_function(),
         )
         .map_err(JsError::from)
@@ -141,6 +155,76 @@ pub fn insert_element(element: ElementId, to: ElementId) -> RawOperationResult<E
         .map(|_| inserted)
 }
 
+/// An empty `<template>` and the inert document its content is created in, elements created
+/// there don't load images or run anything.
+#[instrument(level = "trace", ret, err)]
+pub fn create_template(document: &Document) -> RawOperationResult<(HtmlTemplateElement, Document)> {
+    document
+        .create_element("template")
+        .and_then(|template| {
+            template
+                .dyn_into::<HtmlTemplateElement>()
+                .map_err(JsValue::from)
+        })
+        .and_then(|template| {
+            template
+                .content()
+                .owner_document()
+                .map(|document| (template, document))
+                .ok_or_else(|| JsValue::from_str("template content has no document"))
+        })
+        .map_err(JsError::from)
+        .map_err(RawOperationError::CompilingTemplate)
+}
+
+#[instrument(level = "trace", ret, err)]
+pub fn fill_template(template: &HtmlTemplateElement, root: ElementId) -> RawOperationResult<()> {
+    template
+        .content()
+        .append_child(root.as_ref())
+        .map(|_| ())
+        .map_err(JsError::from)
+        .map_err(RawOperationError::CompilingTemplate)
+}
+
+/// A deep copy of the template's content, adopted by `document`.
+#[instrument(level = "trace", ret, err)]
+pub fn clone_template(
+    document: &Document,
+    template: &HtmlTemplateElement,
+) -> RawOperationResult<Element> {
+    template
+        .content()
+        .first_element_child()
+        .ok_or_else(|| JsValue::from_str("template is empty"))
+        .and_then(|root| document.import_node_with_deep(&root, true))
+        .and_then(|clone| clone.dyn_into::<Element>().map_err(JsValue::from))
+        .map_err(JsError::from)
+        .map_err(RawOperationError::CloningTemplate)
+}
+
+/// Inserts (or moves) `element` into `to`, before `before` or last when it's `None`.
+#[instrument(level = "trace", ret, err)]
+pub fn insert_element_before(
+    element: ElementId,
+    to: ElementId,
+    before: Option<Element>,
+) -> RawOperationResult<ElementId> {
+    let inserted = element.clone();
+    to.as_ref()
+        .insert_before(
+            element.as_ref(),
+            before.as_ref().map(|before| -> &Node { before }),
+        )
+        .map_err(JsError::from)
+        .map_err(|source| RawOperationError::InsertElement {
+            to: DebugOf::new(&to),
+            element: DebugOf::new(&element),
+            source,
+        })
+        .map(|_| inserted)
+}
+
 #[instrument(level = "trace", ret, err)]
 pub fn set_attribute(
     element: ElementId,
@@ -149,12 +233,12 @@ pub fn set_attribute(
 ) -> RawOperationResult<(AttributeName, Option<AttributeValue>)> {
     let old = element
         .as_ref()
-        .get_attribute(attribute.as_ref())
+        .get_attribute(name(&attribute))
         .map(From::from);
     match value {
         Some(value) => element
             .as_ref()
-            .set_attribute(attribute.as_ref(), value.as_ref())
+            .set_attribute(name(&attribute), value.as_ref())
             .map_err(JsError::from)
             .map_err(|source| RawOperationError::SetAttribute {
                 element: DebugOf::new(&element),
@@ -165,7 +249,7 @@ pub fn set_attribute(
             .map(|_| (attribute.clone(), old)),
         None => element
             .as_ref()
-            .remove_attribute(attribute.as_ref())
+            .remove_attribute(name(&attribute))
             .map_err(JsError::from)
             .map_err(|source| RawOperationError::SetAttribute {
                 element: DebugOf::new(&element),
@@ -208,6 +292,102 @@ pub fn remove_element(from_parent: ElementId, element: ElementId) -> RawOperatio
         )
 }
 
+/// Adds a class through `classList`, returning whether the element had it already.
+#[instrument(level = "trace", ret, err)]
+pub fn add_class(element: ElementId, class: &AttributeValue) -> RawOperationResult<bool> {
+    let classes = element.as_ref().class_list();
+    let present = classes.contains(class.as_ref());
+    classes
+        .add_1(class.as_ref())
+        .map_err(JsError::from)
+        .map_err(|source| RawOperationError::ClassList {
+            element: DebugOf::new(&element),
+            class: class.clone(),
+            source,
+        })
+        .map(|()| present)
+}
+
+/// Removes a class through `classList`, returning whether the element had it.
+#[instrument(level = "trace", ret, err)]
+pub fn remove_class(element: ElementId, class: &AttributeValue) -> RawOperationResult<bool> {
+    let classes = element.as_ref().class_list();
+    let present = classes.contains(class.as_ref());
+    classes
+        .remove_1(class.as_ref())
+        .map_err(JsError::from)
+        .map_err(|source| RawOperationError::ClassList {
+            element: DebugOf::new(&element),
+            class: class.clone(),
+            source,
+        })
+        .map(|()| present)
+}
+
+/// Sets (or removes, for `None`) a single inline style property, returning the previous value.
+#[instrument(level = "trace", ret, err)]
+pub fn set_style(
+    element: ElementId,
+    property: &AttributeName,
+    value: Option<&AttributeValue>,
+) -> RawOperationResult<Option<AttributeValue>> {
+    let style = element
+        .as_ref()
+        .dyn_ref::<HtmlElement>()
+        .map(HtmlElement::style)
+        .or_else(|| {
+            element
+                .as_ref()
+                .dyn_ref::<SvgElement>()
+                .map(SvgElement::style)
+        })
+        .ok_or_else(|| RawOperationError::NoInlineStyle {
+            element: DebugOf::new(&element),
+        })?;
+    let previous = style
+        .get_property_value(name(&property))
+        .map(|previous| (!previous.is_empty()).then(|| AttributeValue::from(previous)));
+    previous
+        .and_then(|previous| {
+            match value {
+                Some(value) => style.set_property(name(&property), value.as_ref()),
+                None => style.remove_property(name(&property)).map(|_| ()),
+            }
+            .map(|()| previous)
+        })
+        .map_err(JsError::from)
+        .map_err(|source| RawOperationError::SetStyle {
+            element: DebugOf::new(&element),
+            property: property.clone(),
+            value: value.cloned(),
+            source,
+        })
+}
+
+/// Sets a JS property of the element, returning its previous value.
+#[instrument(level = "trace", ret, err)]
+pub fn set_property(
+    element: ElementId,
+    property: &PropertyName,
+    value: &JsValue,
+) -> RawOperationResult<JsValue> {
+    let target: &JsValue = element.as_ref().as_ref();
+    let name = JsValue::from_str(property.as_ref());
+    js_sys::Reflect::get(target, &name)
+        .and_then(|previous| {
+            js_sys::Reflect::set(target, &name, value).and_then(|set| match set {
+                true => Ok(previous),
+                false => Err(JsValue::from_str("property is read-only")),
+            })
+        })
+        .map_err(JsError::from)
+        .map_err(|source| RawOperationError::SetProperty {
+            element: DebugOf::new(&element),
+            property: property.clone(),
+            source,
+        })
+}
+
 #[instrument(level = "trace", ret)]
 pub fn set_text(element: ElementId, text: Option<AttributeValue>) -> Option<AttributeValue> {
     let old = element.as_ref().text_content().map(AttributeValue::from);
@@ -217,22 +397,120 @@ pub fn set_text(element: ElementId, text: Option<AttributeValue>) -> Option<Attr
     old
 }
 
+/// Options of a `<select multiple>` are controlled with their values joined by this separator.
+pub const SELECTED_VALUES_SEPARATOR: char = '\n';
+
+/// Whether the element is contenteditable. `is_content_editable()` is computed and always
+/// `false` for detached elements, which is how new subtrees are built, so the element's own
+/// `contenteditable` is checked first.
+pub fn is_editable(element: &HtmlElement) -> bool {
+    !matches!(element.content_editable().as_str(), "false" | "inherit")
+        || element.is_content_editable()
+}
+
+/// Element whose value can be controlled with [`set_input_value`].
+enum FormControl<'a> {
+    Input(&'a HtmlInputElement),
+    TextArea(&'a HtmlTextAreaElement),
+    Select(&'a HtmlSelectElement),
+    Editable(&'a HtmlElement),
+}
+
+impl<'a> FormControl<'a> {
+    fn of(element: &'a Element) -> Option<Self> {
+        element
+            .dyn_ref()
+            .map(Self::Input)
+            .or_else(|| element.dyn_ref().map(Self::TextArea))
+            .or_else(|| element.dyn_ref().map(Self::Select))
+            .or_else(|| {
+                element
+                    .dyn_ref::<HtmlElement>()
+                    .filter(|element| is_editable(element))
+                    .map(Self::Editable)
+            })
+    }
+
+    fn value(&self) -> String {
+        match self {
+            Self::Input(input) => input.value(),
+            Self::TextArea(textarea) => textarea.value(),
+            Self::Select(select) if select.multiple() => {
+                selected_values(select).join(&SELECTED_VALUES_SEPARATOR.to_string())
+            }
+            Self::Select(select) => select.value(),
+            Self::Editable(editable) => editable.text_content().unwrap_or_default(),
+        }
+    }
+
+    fn set_value(&self, value: &str) {
+        match self {
+            Self::Input(input) => input.set_value(value),
+            Self::TextArea(textarea) => textarea.set_value(value),
+            Self::Select(select) if select.multiple() => {
+                let selected = value.split(SELECTED_VALUES_SEPARATOR).collect::<Vec<_>>();
+                options(select).for_each(|option| {
+                    option.set_selected(selected.contains(&option.value().as_str()))
+                });
+            }
+            Self::Select(select) => select.set_value(value),
+            Self::Editable(editable) => editable.set_text_content(Some(value)),
+        }
+    }
+}
+
+/// Sets the live value of a form control: the `.value` of `<input>`, `<textarea>` and single
+/// `<select>` elements, the selected options of a `<select multiple>` and the text of a
+/// contenteditable element.
 #[instrument(level = "trace", ret)]
 pub fn set_input_value(
     element: ElementId,
     value: AttributeValue,
 ) -> RawOperationResult<AttributeValue> {
+    let control =
+        FormControl::of(element.as_ref()).ok_or_else(|| RawOperationError::NotAFormControl {
+            element: DebugOf::new(element.as_ref()),
+        })?;
+    let old = control.value();
+    if old != value.as_ref() {
+        control.set_value(value.as_ref());
+    }
+    Ok(old.into())
+}
+
+/// The live value [`set_input_value`] sets, `None` when the element is not a form control.
+pub fn input_value(element: &Element) -> Option<String> {
+    FormControl::of(element).map(|control| control.value())
+}
+
+fn options(select: &HtmlSelectElement) -> impl Iterator<Item = HtmlOptionElement> + '_ {
+    let options = select.options();
+    (0..options.length())
+        .filter_map(move |index| options.item(index))
+        .filter_map(|option| option.dyn_into::<HtmlOptionElement>().ok())
+}
+
+/// Values of the selected options, in document order.
+pub fn selected_values(select: &HtmlSelectElement) -> Vec<String> {
+    options(select)
+        .filter(|option| option.selected())
+        .map(|option| option.value())
+        .collect()
+}
+
+/// Sets `.checked` of a checkbox or radio `<input>`.
+#[instrument(level = "trace", ret)]
+pub fn set_checked(element: ElementId, checked: bool) -> RawOperationResult<bool> {
     element
         .as_ref()
-        .clone()
-        .dyn_into::<HtmlInputElement>()
-        .map_err(|actual| RawOperationError::NotAnInputElement {
-            element: DebugOf::new(&actual),
+        .dyn_ref::<HtmlInputElement>()
+        .ok_or_else(|| RawOperationError::NotACheckable {
+            element: DebugOf::new(element.as_ref()),
         })
         .map(|input| {
-            let old = input.value();
-            input.set_value(value.as_ref());
-            old.into()
+            let old = input.checked();
+            input.set_checked(checked);
+            old
         })
 }
 
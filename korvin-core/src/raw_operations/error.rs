@@ -1,4 +1,4 @@
-use crate::data::{AttributeName, AttributeValue, TagName};
+use crate::data::{AttributeName, AttributeValue, PropertyName, TagName};
 use std::any::TypeId;
 use thiserror::Error;
 use wasm_bindgen::JsValue;
@@ -73,6 +73,27 @@ pub enum RawOperationError {
         value: Option<AttributeValue>,
         source: JsError,
     },
+    #[error("Changing class {class} of {element:?}: {source}")]
+    ClassList {
+        element: DebugOf,
+        class: AttributeValue,
+        source: JsError,
+    },
+    #[error("Setting style on element {element:?}: ({property} -> {value:?}): {source}")]
+    SetStyle {
+        element: DebugOf,
+        property: AttributeName,
+        value: Option<AttributeValue>,
+        source: JsError,
+    },
+    #[error("Expected {element:?} to be an HTML or SVG element with inline style.")]
+    NoInlineStyle { element: DebugOf },
+    #[error("Setting property on element {element:?}: ({property}): {source}")]
+    SetProperty {
+        element: DebugOf,
+        property: PropertyName,
+        source: JsError,
+    },
     #[error("Unhandled js error: {0}")]
     UnhandledJs(#[from] JsError),
     #[error("Element mismatch found on removal, this is a fatal error and should be reported to korvin developers. ({expected:?} != {got:?})")]
@@ -87,8 +108,16 @@ pub enum RawOperationError {
         with: DebugOf,
         source: JsError,
     },
-    #[error("Expected {element:?} to be an <input> element.")]
-    NotAnInputElement { element: DebugOf },
+    #[error(
+        "Expected {element:?} to be an <input>, <textarea>, <select> or contenteditable element."
+    )]
+    NotAFormControl { element: DebugOf },
+    #[error("Expected {element:?} to be an <input> that can be checked.")]
+    NotACheckable { element: DebugOf },
+    #[error("Compiling a <template>: {0}")]
+    CompilingTemplate(#[source] JsError),
+    #[error("Cloning a <template>: {0}")]
+    CloningTemplate(#[source] JsError),
 }
 
 pub type RawOperationResult<T> = std::result::Result<T, RawOperationError>;
@@ -0,0 +1,197 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Weak,
+};
+
+use crate::{
+    data::{ElementId, EventListenerWrapper},
+    dom_executor::{recycle::PoolStats, template::TemplateStats},
+};
+
+/// Counted for every runtime on the thread together, see [`thread`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ThreadStats {
+    /// elements created by a runtime that are still referenced by a snapshot (or anything else),
+    /// pooled ones included
+    pub live_elements: usize,
+    /// event listener closures that were not dropped yet
+    pub live_closures: usize,
+    /// event listeners currently attached to an element
+    pub listener_registrations: usize,
+}
+
+/// Counted for a single runtime, see [`crate::Runtime::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuntimeStats {
+    /// values held by this runtime's value cache, at most its capacity. Only views built through
+    /// [`crate::Runtime::build`] cache their values there, otherwise it stays empty.
+    pub cached_values: usize,
+    /// removed elements kept for reuse, see [`crate::Runtime::with_element_pool`]
+    pub pooled_elements: usize,
+    /// elements taken from the pool instead of being created
+    pub reused_elements: usize,
+    /// `<template>`s compiled for subtrees marked with
+    /// [`crate::element_builder::AsElementBuilder::template`]
+    pub compiled_templates: usize,
+    /// subtrees built by cloning a compiled template
+    pub cloned_templates: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Counter {
+    Elements,
+    Closures,
+    ListenerRegistrations,
+}
+
+#[derive(Default)]
+struct Counters {
+    elements: Cell<usize>,
+    closures: Cell<usize>,
+    listener_registrations: Cell<usize>,
+}
+
+impl Counters {
+    fn get(&self, counter: Counter) -> &Cell<usize> {
+        match counter {
+            Counter::Elements => &self.elements,
+            Counter::Closures => &self.closures,
+            Counter::ListenerRegistrations => &self.listener_registrations,
+        }
+    }
+}
+
+struct ListenerOwnership {
+    event: String,
+    element: String,
+    element_alive: Weak<LiveToken>,
+    closure_alive: Weak<LiveToken>,
+    reported: bool,
+}
+
+thread_local! {
+    static COUNTERS: Counters = Default::default();
+    /// `None` when leak checking is disabled
+    static LISTENER_OWNERSHIP: RefCell<Option<Vec<ListenerOwnership>>> = Default::default();
+}
+
+/// Counts itself in the given counter for as long as it's alive.
+#[derive(Debug)]
+pub(crate) struct LiveToken(Counter);
+
+impl LiveToken {
+    pub(crate) fn new(counter: Counter) -> Self {
+        COUNTERS.with(|counters| {
+            let count = counters.get(counter);
+            count.set(count.get() + 1)
+        });
+        Self(counter)
+    }
+}
+
+impl Drop for LiveToken {
+    fn drop(&mut self) {
+        COUNTERS.with(|counters| {
+            let count = counters.get(self.0);
+            count.set(count.get().saturating_sub(1))
+        });
+    }
+}
+
+/// A listener closure that outlived the element it was attached to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeakedClosure {
+    pub event: String,
+    pub element: String,
+}
+
+/// Elements, closures and listeners of every runtime on this thread, a program with its
+/// debugger overlay included.
+pub fn thread() -> ThreadStats {
+    COUNTERS.with(|counters| ThreadStats {
+        live_elements: counters.elements.get(),
+        live_closures: counters.closures.get(),
+        listener_registrations: counters.listener_registrations.get(),
+    })
+}
+
+pub(crate) fn current(
+    cached_values: usize,
+    element_pool: PoolStats,
+    templates: TemplateStats,
+) -> RuntimeStats {
+    RuntimeStats {
+        cached_values,
+        pooled_elements: element_pool.pooled,
+        reused_elements: element_pool.reused,
+        compiled_templates: templates.compiled,
+        cloned_templates: templates.cloned,
+    }
+}
+
+pub(crate) fn set_leak_check(enabled: bool) {
+    LISTENER_OWNERSHIP.with(|ownership| {
+        let mut ownership = ownership.borrow_mut();
+        match enabled {
+            true => {
+                ownership.get_or_insert_with(Default::default);
+            }
+            false => *ownership = None,
+        }
+    })
+}
+
+pub(crate) fn leak_check_enabled() -> bool {
+    LISTENER_OWNERSHIP.with(|ownership| ownership.borrow().is_some())
+}
+
+/// remembers which element the closure was attached to, only when leak checking is enabled
+pub(crate) fn register_listener<EventKind>(
+    element: &ElementId,
+    listener: &EventListenerWrapper<EventKind>,
+) {
+    let Some(element_alive) = element.live() else {
+        return;
+    };
+    LISTENER_OWNERSHIP.with(|ownership| {
+        if let Some(ownership) = ownership.borrow_mut().as_mut() {
+            ownership.push(ListenerOwnership {
+                event: listener.name.to_string(),
+                element: format!("{element:?}"),
+                element_alive,
+                closure_alive: listener.closure.live(),
+                reported: false,
+            })
+        }
+    })
+}
+
+/// Closures that are still alive even though the element they were attached to was dropped.
+/// Newly found leaks are reported with [`tracing::error`] only once.
+pub(crate) fn leaked_closures() -> Vec<LeakedClosure> {
+    LISTENER_OWNERSHIP.with(|ownership| {
+        let mut ownership = ownership.borrow_mut();
+        let Some(ownership) = ownership.as_mut() else {
+            return vec![];
+        };
+        ownership.retain(|entry| entry.closure_alive.strong_count() > 0);
+        ownership
+            .iter_mut()
+            .filter(|entry| entry.element_alive.strong_count() == 0)
+            .map(|entry| {
+                if !entry.reported {
+                    entry.reported = true;
+                    tracing::error!(
+                        event = %entry.event,
+                        element = %entry.element,
+                        "closure is still alive after its element was removed"
+                    );
+                }
+                LeakedClosure {
+                    event: entry.event.clone(),
+                    element: entry.element.clone(),
+                }
+            })
+            .collect()
+    })
+}
@@ -0,0 +1,272 @@
+//! Component stylesheets declared in Rust. Every stylesheet gets a scope class generated from
+//! its name and contents, `&` in selectors stands for that class:
+//!
+//! ```ignore
+//! static COUNTER: Lazy<Stylesheet> = Lazy::new(|| {
+//!     Stylesheet::new("counter")
+//!         .rule("&", [("display", "flex"), ("gap", "1rem")])
+//!         .nest("button", |button| {
+//!             button
+//!                 .rule("&", [("padding", "0.5rem")])
+//!                 .rule("&:hover", [("background", "lightblue")])
+//!         })
+//!         .media("(max-width: 600px)", |small| small.rule("&", [("flex-direction", "column")]))
+//!         .build()
+//! });
+//!
+//! "div".stylesheet(&COUNTER).child("button".text("+"))
+//! ```
+//!
+//! The stylesheet is injected into a `<style>` in `<head>` when the first element using it is
+//! built and removed once no built element uses it anymore.
+
+use crate::{data::AttributeValue, element_builder::calculate_hash};
+use std::{cell::RefCell, collections::HashMap, fmt::Write, rc::Rc};
+
+#[derive(Debug, Clone)]
+enum Block {
+    Rule {
+        selector: String,
+        declarations: Vec<(String, String)>,
+    },
+    Nested {
+        selector: String,
+        blocks: Vec<Block>,
+    },
+    Media {
+        query: String,
+        blocks: Vec<Block>,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StylesheetBuilder {
+    name: String,
+    blocks: Vec<Block>,
+}
+
+impl StylesheetBuilder {
+    /// Selectors without `&` apply to descendants of the enclosing selector.
+    pub fn rule<'a>(
+        mut self,
+        selector: &str,
+        declarations: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Self {
+        self.blocks.push(Block::Rule {
+            selector: selector.to_string(),
+            declarations: declarations
+                .into_iter()
+                .map(|(property, value)| (property.to_string(), value.to_string()))
+                .collect(),
+        });
+        self
+    }
+
+    /// Rules declared by `nested` are relative to `selector`.
+    pub fn nest(mut self, selector: &str, nested: impl FnOnce(Self) -> Self) -> Self {
+        self.blocks.push(Block::Nested {
+            selector: selector.to_string(),
+            blocks: nested(Self::default()).blocks,
+        });
+        self
+    }
+
+    pub fn media(mut self, query: &str, nested: impl FnOnce(Self) -> Self) -> Self {
+        self.blocks.push(Block::Media {
+            query: query.to_string(),
+            blocks: nested(Self::default()).blocks,
+        });
+        self
+    }
+
+    pub fn build(self) -> Stylesheet {
+        const PLACEHOLDER: &str = "\u{0}scope";
+        let mut css = String::new();
+        render(&mut css, &[format!(".{PLACEHOLDER}")], &self.blocks);
+        let name = self
+            .name
+            .chars()
+            .map(
+                |c| match c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    true => c,
+                    false => '-',
+                },
+            )
+            .collect::<String>();
+        let scope = format!("korvin-{name}-{:08x}", calculate_hash(&css) as u32);
+        Stylesheet {
+            css: css.replace(PLACEHOLDER, &scope),
+            scope,
+        }
+    }
+}
+
+/// Splits a selector list on its top-level commas, leaving `:is(a, b)` and `[title="a, b"]`
+/// in one piece.
+fn selector_list(selectors: &str) -> Vec<&str> {
+    let mut list = vec![];
+    let (mut depth, mut quote, mut start) = (0_usize, None, 0);
+    selectors
+        .char_indices()
+        .for_each(|(index, c)| match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                list.push(&selectors[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        });
+    list.push(&selectors[start..]);
+    list
+}
+
+/// Joins `selector` to every one of the enclosing selectors.
+fn resolve(parents: &[String], selector: &str) -> Vec<String> {
+    parents
+        .iter()
+        .flat_map(|parent| {
+            selector_list(selector)
+                .into_iter()
+                .map(str::trim)
+                .map(move |selector| match selector.contains('&') {
+                    true => selector.replace('&', parent),
+                    false => format!("{parent} {selector}"),
+                })
+        })
+        .collect()
+}
+
+fn render(css: &mut String, parents: &[String], blocks: &[Block]) {
+    blocks.iter().for_each(|block| match block {
+        Block::Rule {
+            selector,
+            declarations,
+        } => {
+            let _ = write!(css, "{} {{", resolve(parents, selector).join(", "));
+            declarations.iter().for_each(|(property, value)| {
+                let _ = write!(css, " {property}: {value};");
+            });
+            css.push_str(" }\n");
+        }
+        Block::Nested { selector, blocks } => render(css, &resolve(parents, selector), blocks),
+        Block::Media { query, blocks } => {
+            let _ = writeln!(css, "@media {query} {{");
+            render(css, parents, blocks);
+            css.push_str("}\n");
+        }
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Stylesheet {
+    scope: String,
+    css: String,
+}
+
+impl Stylesheet {
+    /// `name` ends up in the scope class, anything but ASCII letters, digits, `_` and `-` is
+    /// replaced with `-`.
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(name: &str) -> StylesheetBuilder {
+        StylesheetBuilder {
+            name: name.to_string(),
+            blocks: Default::default(),
+        }
+    }
+
+    /// Class added to elements using the stylesheet.
+    pub fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    /// The scoped CSS, e.g. to inline into server rendered pages.
+    pub fn to_css(&self) -> &str {
+        &self.css
+    }
+}
+
+struct Injected {
+    users: usize,
+    style: web_sys::Element,
+}
+
+thread_local! {
+    static INJECTED: RefCell<HashMap<AttributeValue, Injected>> = Default::default();
+}
+
+/// Keeps the stylesheet injected for as long as it's alive.
+#[derive(Debug, PartialEq)]
+pub struct StylesheetGuard {
+    scope: AttributeValue,
+}
+
+impl StylesheetGuard {
+    pub(crate) fn acquire(scope: &AttributeValue, css: &AttributeValue) -> Rc<Self> {
+        INJECTED.with(|injected| {
+            let mut injected = injected.borrow_mut();
+            match injected.get_mut(scope) {
+                Some(injected) => injected.users += 1,
+                None => match inject(scope, css) {
+                    Ok(style) => {
+                        injected.insert(scope.clone(), Injected { users: 1, style });
+                    }
+                    Err(message) => tracing::error!(?message, %scope, "injecting stylesheet"),
+                },
+            }
+        });
+        Rc::new(Self {
+            scope: scope.clone(),
+        })
+    }
+}
+
+/// Number of built elements currently using the stylesheet, it's injected while this is
+/// non-zero.
+pub fn users(stylesheet: &Stylesheet) -> usize {
+    INJECTED.with(|injected| {
+        injected
+            .borrow()
+            .get(&AttributeValue::from(stylesheet.scope()))
+            .map(|injected| injected.users)
+            .unwrap_or_default()
+    })
+}
+
+impl Drop for StylesheetGuard {
+    fn drop(&mut self) {
+        INJECTED.with(|injected| {
+            let mut injected = injected.borrow_mut();
+            let unused = injected
+                .get_mut(&self.scope)
+                .map(|injected| {
+                    injected.users -= 1;
+                    injected.users == 0
+                })
+                .unwrap_or_default();
+            if unused {
+                if let Some(Injected { style, .. }) = injected.remove(&self.scope) {
+                    style.remove();
+                }
+            }
+        })
+    }
+}
+
+fn inject(scope: &AttributeValue, css: &AttributeValue) -> Result<web_sys::Element, String> {
+    crate::DOCUMENT.with(|document| {
+        let head = document.head().ok_or("no <head>")?;
+        let style = document
+            .create_element("style")
+            .map_err(|e| format!("{e:?}"))?;
+        style
+            .set_attribute("data-korvin-scope", scope.as_ref())
+            .map_err(|e| format!("{e:?}"))?;
+        style.set_text_content(Some(css.as_ref()));
+        head.append_child(&style).map_err(|e| format!("{e:?}"))?;
+        Ok(style)
+    })
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::executor;
+use korvin_core::flavors::elm_like::cmd::Cmd;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+#[derive(Debug, PartialEq)]
+enum Message {
+    Loaded(u32),
+    Timeout,
+    Child(u32),
+}
+
+#[test]
+fn none_produces_no_messages() {
+    let (mut pool, executor) = executor();
+    let messages = pool.run_until(Cmd::<Message>::none().collect(&executor));
+    assert!(messages.is_empty());
+    assert!(executor.slept.borrow().is_empty());
+}
+
+#[test]
+fn batch_runs_every_command() {
+    let (mut pool, executor) = executor();
+    let cmd = Cmd::batch([
+        Cmd::perform(async { Message::Loaded(1) }),
+        Cmd::delay(Duration::from_secs(3), Message::Timeout),
+        Cmd::perform(async { 2 }).map(Message::Child),
+    ]);
+    let mut messages = pool.run_until(cmd.collect(&executor));
+    messages.sort_by_key(|message| format!("{message:?}"));
+    assert_eq!(
+        messages,
+        vec![Message::Child(2), Message::Loaded(1), Message::Timeout]
+    );
+    assert_eq!(*executor.slept.borrow(), vec![Duration::from_secs(3)]);
+}
+
+#[test]
+fn run_sends_results() {
+    let (mut pool, executor) = executor();
+    let received = Rc::new(RefCell::new(vec![]));
+    Cmd::batch([
+        Cmd::perform(async { Message::Loaded(7) }),
+        Cmd::delay(Duration::from_millis(10), Message::Timeout),
+    ])
+    .run(&executor, {
+        let received = received.clone();
+        move |message| received.borrow_mut().push(message)
+    });
+    assert!(
+        received.borrow().is_empty(),
+        "nothing runs before the executor"
+    );
+    pool.run();
+    assert_eq!(
+        *received.borrow(),
+        vec![Message::Loaded(7), Message::Timeout]
+    );
+}
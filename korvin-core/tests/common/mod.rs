@@ -0,0 +1,45 @@
+//! Shared by the native tests of the elm-like flavor.
+
+use futures::{
+    executor::{LocalPool, LocalSpawner},
+    future::{self, LocalBoxFuture},
+    task::LocalSpawnExt,
+    FutureExt,
+};
+use korvin_core::flavors::elm_like::cmd::Executor;
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+/// Sleeps the [`TestExecutor`] finishes, later ones never do so that intervals stop.
+pub const SLEEPS: usize = 10;
+
+/// Runs tasks on a [`LocalPool`] and doesn't wait at all, only remembers how long it was asked
+/// to sleep.
+#[derive(Clone)]
+pub struct TestExecutor {
+    spawner: LocalSpawner,
+    pub slept: Rc<RefCell<Vec<Duration>>>,
+}
+
+impl Executor for TestExecutor {
+    fn spawn(&self, task: LocalBoxFuture<'static, ()>) {
+        self.spawner.spawn_local(task).expect("spawning failed");
+    }
+
+    fn sleep(&self, duration: Duration) -> LocalBoxFuture<'static, ()> {
+        let mut slept = self.slept.borrow_mut();
+        if slept.len() >= SLEEPS {
+            return future::pending().boxed_local();
+        }
+        slept.push(duration);
+        future::ready(()).boxed_local()
+    }
+}
+
+pub fn executor() -> (LocalPool, TestExecutor) {
+    let pool = LocalPool::new();
+    let executor = TestExecutor {
+        spawner: pool.spawner(),
+        slept: Default::default(),
+    };
+    (pool, executor)
+}
@@ -0,0 +1,50 @@
+use futures::{FutureExt, StreamExt};
+use korvin_core::flavors::elm_like::Communicator;
+
+#[derive(Debug, PartialEq)]
+enum Message {
+    Row { index: usize, message: RowMessage },
+}
+
+#[derive(Debug, PartialEq)]
+enum RowMessage {
+    Clicked,
+    Renamed(String),
+}
+
+#[test]
+fn map_chains_closures_with_context() {
+    let (mut rx, communicator) = Communicator::<Message>::create();
+    let index = 3;
+    let row = communicator.map(move |message| Message::Row { index, message });
+    let renamed = row.map(|name: &str| RowMessage::Renamed(name.to_string()));
+    row.send(RowMessage::Clicked);
+    renamed.send("korvin");
+    assert_eq!(
+        rx.next().now_or_never().flatten(),
+        Some(Message::Row {
+            index: 3,
+            message: RowMessage::Clicked
+        })
+    );
+    assert_eq!(
+        rx.next().now_or_never().flatten(),
+        Some(Message::Row {
+            index: 3,
+            message: RowMessage::Renamed("korvin".to_string())
+        })
+    );
+}
+
+#[test]
+fn sending_after_close_is_an_error() {
+    let (rx, communicator) = Communicator::<Message>::create();
+    let row = communicator.map(|message| Message::Row { index: 0, message });
+    assert!(row.try_send(RowMessage::Clicked).is_ok());
+    communicator.close();
+    assert!(row.is_closed());
+    assert!(row.try_send(RowMessage::Clicked).is_err());
+    // only logs
+    row.send(RowMessage::Clicked);
+    drop(rx);
+}
@@ -0,0 +1,120 @@
+use korvin_core::flavors::elm_like::form::{self, validators, Field, FormErrors, Validator};
+
+#[derive(Debug, PartialEq)]
+struct Person {
+    name: String,
+    age: u8,
+    nickname: Option<String>,
+}
+
+fn age() -> Field<u8> {
+    Field::new("").validate(validators::min(18).and(validators::max(99)))
+}
+
+#[test]
+fn invalid_input_is_kept_with_its_errors() {
+    let mut field = age();
+    field.set("abc");
+    assert_eq!(field.raw(), "abc");
+    assert_eq!(field.value(), None);
+    assert_eq!(field.errors(), ["invalid digit found in string"]);
+
+    field.set("42");
+    assert_eq!(field.value(), Some(&42));
+    assert!(field.is_valid());
+}
+
+#[test]
+fn validators_compose() {
+    let mut field = Field::<i32>::new("0")
+        .validate(validators::min(1))
+        .validate(Validator::check(|value| value % 2 == 0, "must be even"));
+    assert_eq!(field.errors(), ["must be at least 1"]);
+
+    field.set("-1");
+    assert_eq!(field.errors(), ["must be at least 1", "must be even"]);
+    assert_eq!(field.value(), Some(&-1));
+
+    let chained = validators::min(1).and(Validator::check(|value| value % 2 == 0, "must be even"));
+    assert_eq!(chained.validate(&-1), Err("must be at least 1".to_string()));
+    assert_eq!(chained.validate(&3), Err("must be even".to_string()));
+    assert_eq!(chained.validate(&4), Ok(()));
+}
+
+#[test]
+fn errors_are_visible_once_touched() {
+    let mut field = age();
+    assert!(!field.is_valid());
+    assert!(field.visible_errors().is_empty());
+    field.touch();
+    assert_eq!(field.visible_errors(), field.errors());
+}
+
+#[test]
+fn optional_fields_accept_blank_input() {
+    let mut field = Field::<String>::new("")
+        .optional()
+        .validate(validators::max_length(3));
+    assert!(field.is_valid());
+    assert_eq!(field.value(), None);
+    field.set("toolong");
+    assert_eq!(field.errors(), ["at most 3 characters"]);
+}
+
+#[test]
+fn submit_collects_every_invalid_field() {
+    let name = Field::<String>::new(" ").validate(validators::non_empty());
+    let age = Field::new("12").validate(validators::min(18u8));
+    let nickname = Field::<String>::new("").optional();
+    let submitted = form::submit(|form| {
+        let name = form.field("name", &name);
+        let age = form.field("age", &age);
+        let nickname = form.field("nickname", &nickname);
+        Some(Person {
+            name: name?,
+            age: age?,
+            nickname,
+        })
+    });
+    assert_eq!(
+        submitted,
+        Err(FormErrors(
+            [
+                ("age", vec!["must be at least 18".to_string()]),
+                ("name", vec!["required".to_string()]),
+            ]
+            .into_iter()
+            .collect()
+        ))
+    );
+}
+
+#[test]
+fn submit_builds_the_value() {
+    let name = Field::<String>::new("Ada");
+    let age = Field::with_value(36u8);
+    let nickname = Field::<String>::new("").optional();
+    let submitted = form::submit(|form| {
+        let name = form.field("name", &name);
+        let age = form.field("age", &age);
+        Some(Person {
+            name: name?,
+            age: age?,
+            nickname: form.field("nickname", &nickname),
+        })
+    });
+    assert_eq!(
+        submitted,
+        Ok(Person {
+            name: "Ada".to_string(),
+            age: 36,
+            nickname: None,
+        })
+    );
+
+    let missing = form::submit(|form| form.required("nickname", &nickname));
+    assert_eq!(
+        missing.map_err(|errors| errors.to_string()),
+        Err("nickname: required\n".to_string())
+    );
+}
@@ -0,0 +1,92 @@
+use korvin_core::{
+    element_builder::{AsElementBuilder, ElementBuilder},
+    html,
+};
+
+fn recipe(builder: ElementBuilder) -> String {
+    format!("{:?}", builder.build())
+}
+
+fn item(label: &str) -> ElementBuilder {
+    "li".text(label)
+}
+
+#[test]
+fn attributes_and_text() {
+    let count = 3;
+    let label = "three";
+    assert_eq!(
+        recipe(html! {
+            <div id="counter" data-count={label} class:even={count % 2 == 0} style:color="red">
+                {count.to_string().as_str()}
+            </div>
+        }),
+        recipe(
+            "div"
+                .attribute("id", "counter")
+                .attribute("data-count", label)
+                .class_if("even", false)
+                .style("color", "red")
+                .text(count.to_string().as_str())
+        ),
+    );
+}
+
+#[test]
+fn keys_bindings_and_self_closing_tags() {
+    assert_eq!(
+        recipe(html! {
+            <form>
+                <input key="name" type="text" bind:value="Ann" />
+                <input type="checkbox" bind:checked={true} />
+            </form>
+        }),
+        recipe(
+            "form"
+                .child(
+                    "input"
+                        .key("name")
+                        .attribute("type", "text")
+                        .input_value("Ann")
+                )
+                .child("input".attribute("type", "checkbox").checked(true))
+        ),
+    );
+}
+
+#[test]
+fn conditionals_loops_and_components() {
+    let todos = ["write", "test"];
+    let view = |empty: bool| {
+        html! {
+            <ul>
+                for (index, todo) in todos.iter().enumerate() {
+                    <li key={index}>{*todo}</li>
+                }
+                if empty {
+                    <li>"nothing to do"</li>
+                } else if todos.len() > 1 {
+                    @item("more than one")
+                } else {
+                    @{"li".text("just one")}
+                }
+            </ul>
+        }
+    };
+    assert_eq!(
+        recipe(view(false)),
+        recipe(
+            "ul".child("li".key(0_usize).text("write"))
+                .child("li".key(1_usize).text("test"))
+                .child("li".text("more than one"))
+        ),
+    );
+    assert_eq!(
+        recipe(view(true)),
+        recipe(
+            "ul".child("li".key(0_usize).text("write"))
+                .child("li".key(1_usize).text("test"))
+                .child("li".text("nothing to do"))
+        ),
+    );
+}
@@ -0,0 +1,9 @@
+use korvin_core::html;
+
+fn main() {
+    let _ = html! {
+        <ul>
+            <li>"one"</span>
+        </ul>
+    };
+}
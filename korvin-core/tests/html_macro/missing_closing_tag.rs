@@ -0,0 +1,8 @@
+use korvin_core::html;
+
+fn main() {
+    let _ = html! {
+        <section>
+            <p>"never closed"</p>
+    };
+}
@@ -0,0 +1,10 @@
+use korvin_core::html;
+
+fn main() {
+    let _ = html! {
+        <p>
+            "label"
+            <span>"child"</span>
+        </p>
+    };
+}
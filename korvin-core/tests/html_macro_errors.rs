@@ -0,0 +1,4 @@
+#[test]
+fn malformed_markup_points_at_the_offending_tag() {
+    trybuild::TestCases::new().compile_fail("tests/html_macro/*.rs");
+}
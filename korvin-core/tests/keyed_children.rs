@@ -0,0 +1,79 @@
+use korvin_core::{
+    component::Component,
+    element_builder::{calculate_hash, AsElementBuilder, DuplicateKey, ElementBuilder},
+};
+
+fn recipe(builder: ElementBuilder) -> String {
+    format!("{:?}", builder.build())
+}
+
+struct Item;
+
+impl Component for Item {
+    type Props = u32;
+
+    fn render(id: &Self::Props) -> ElementBuilder {
+        "li".text(*id)
+    }
+}
+
+#[test]
+fn keys_every_rendered_child() {
+    let items = [(1_u32, "one"), (2, "two")];
+    assert_eq!(
+        recipe("ul".keyed_children(items, |(id, _)| *id, |(_, label)| "li".text(label))),
+        recipe(
+            "ul".child("li".key(1_u32).text("one"))
+                .child("li".key(2_u32).text("two"))
+        ),
+    );
+}
+
+#[test]
+fn unique_keys_have_no_duplicates() {
+    let recipe = "ul"
+        .keyed_children(0..3_usize, |id| *id, |id| "li".text(id))
+        .child("li".text("unkeyed"))
+        .child("li".text("unkeyed"))
+        // the same key on another tag is told apart by the tag
+        .child("p".key(0_usize))
+        .build();
+    assert_eq!(recipe.duplicate_keys(), vec![]);
+}
+
+#[test]
+fn duplicate_keys_are_reported_with_their_positions() {
+    let recipe = "ul"
+        .keyed_children([1_u32, 2, 1, 3, 2, 1], |id| *id, |id| "li".text(id))
+        .build();
+    assert_eq!(
+        recipe.duplicate_keys(),
+        vec![
+            DuplicateKey {
+                key: calculate_hash(&1_u32),
+                kind: "li".to_string(),
+                positions: vec![0, 2, 5],
+            },
+            DuplicateKey {
+                key: calculate_hash(&2_u32),
+                kind: "li".to_string(),
+                positions: vec![1, 4],
+            },
+        ],
+    );
+}
+
+#[test]
+fn duplicate_memoized_components_are_reported() {
+    let recipe = "ul"
+        .keyed_children([7_u32, 7], |id| *id, Item::memo)
+        .build();
+    assert_eq!(
+        recipe.duplicate_keys(),
+        vec![DuplicateKey {
+            key: calculate_hash(&7_u32),
+            kind: std::any::type_name::<Item>().to_string(),
+            positions: vec![0, 1],
+        }],
+    );
+}
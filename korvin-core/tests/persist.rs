@@ -0,0 +1,113 @@
+mod common;
+
+use eyre::{eyre, Result};
+use futures::future::LocalBoxFuture;
+use korvin_core::flavors::elm_like::persist::{MemoryStore, Persistence, Store};
+use serde::{Deserialize, Serialize};
+use std::{cell::Cell, rc::Rc};
+
+/// counts writes to the wrapped store
+#[derive(Clone, Default)]
+struct CountingStore {
+    inner: MemoryStore,
+    saves: Rc<Cell<usize>>,
+}
+
+impl Store for CountingStore {
+    fn load(&self, key: &str) -> LocalBoxFuture<'static, Result<Option<String>>> {
+        self.inner.load(key)
+    }
+
+    fn save(&self, key: &str, value: String) -> LocalBoxFuture<'static, Result<()>> {
+        self.saves.set(self.saves.get() + 1);
+        self.inner.save(key, value)
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Preferences {
+    theme: String,
+    font_size: u32,
+}
+
+#[test]
+fn nothing_stored_restores_none() -> Result<()> {
+    let persistence = Persistence::new("preferences", MemoryStore::default());
+    let restored = futures::executor::block_on(persistence.restore::<Preferences>())?;
+    assert_eq!(restored, None);
+    Ok(())
+}
+
+#[test]
+fn burst_of_saves_is_written_once() -> Result<()> {
+    let store = CountingStore::default();
+    let persistence = Persistence::new("preferences", store.clone());
+    let (mut pool, executor) = common::executor();
+    (10..13).for_each(|font_size| {
+        persistence.save(
+            &Preferences {
+                theme: "dark".to_string(),
+                font_size,
+            },
+            &executor,
+        )
+    });
+    pool.run();
+    assert_eq!(store.saves.get(), 1);
+    let restored = pool.run_until(persistence.restore::<Preferences>())?;
+    assert_eq!(
+        restored,
+        Some(Preferences {
+            theme: "dark".to_string(),
+            font_size: 12
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn old_versions_are_migrated() -> Result<()> {
+    let store = MemoryStore::default();
+    // version 0 only had a boolean
+    store.insert("preferences", r#"{"version":0,"model":{"dark":true}}"#);
+    let persistence = Persistence::new("preferences", store)
+        .version(2)
+        .migration(0, |mut model| {
+            let dark = model["dark"].as_bool().ok_or_else(|| eyre!("no dark"))?;
+            model["theme"] = match dark {
+                true => "dark",
+                false => "light",
+            }
+            .into();
+            Ok(model)
+        })
+        .migration(1, |mut model| {
+            model["font_size"] = 14.into();
+            Ok(model)
+        });
+    let restored = futures::executor::block_on(persistence.restore::<Preferences>())?;
+    assert_eq!(
+        restored,
+        Some(Preferences {
+            theme: "dark".to_string(),
+            font_size: 14
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn newer_versions_are_rejected() {
+    let persistence = Persistence::new("preferences", MemoryStore::default()).version(1);
+    assert!(persistence
+        .decode::<Preferences>(r#"{"version":2,"model":{"theme":"dark","font_size":1}}"#)
+        .is_err());
+}
+
+#[test]
+fn missing_migration_is_an_error() {
+    let persistence = Persistence::new("preferences", MemoryStore::default()).version(1);
+    assert!(persistence
+        .decode::<Preferences>(r#"{"version":0,"model":{"theme":"dark","font_size":1}}"#)
+        .is_err());
+}
@@ -0,0 +1,96 @@
+use korvin_core::flavors::elm_like::router::{RouteTable, Url};
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    Home,
+    User { id: u32, tab: Option<String> },
+    Files(String),
+    Admin(AdminRoute),
+    NotFound(String),
+}
+
+#[derive(Debug, PartialEq)]
+enum AdminRoute {
+    Dashboard,
+    Settings,
+    Unknown(Vec<String>),
+}
+
+fn routes() -> RouteTable<Route> {
+    let admin = RouteTable::new(|url| AdminRoute::Unknown(url.segments.clone()))
+        .route("/", |_| Some(AdminRoute::Dashboard))
+        .route("/settings", |_| Some(AdminRoute::Settings));
+    RouteTable::new(|url| Route::NotFound(url.to_string()))
+        .route("/", |_| Some(Route::Home))
+        .route("/users/:id", |params| {
+            Some(Route::User {
+                id: params.parse("id")?,
+                tab: params.query("tab").map(str::to_string),
+            })
+        })
+        .route("/files/*path", |params| {
+            Some(Route::Files(params.get("path")?.to_string()))
+        })
+        .nest("/admin", admin, Route::Admin)
+}
+
+#[test]
+fn parses_paths_and_captures() {
+    let routes = routes();
+    assert_eq!(routes.parse("/"), Route::Home);
+    assert_eq!(routes.parse(""), Route::Home);
+    assert_eq!(
+        routes.parse("/users/7?tab=posts#bio"),
+        Route::User {
+            id: 7,
+            tab: Some("posts".to_string())
+        }
+    );
+    assert_eq!(
+        routes.parse("/files/docs/readme.md"),
+        Route::Files("docs/readme.md".to_string())
+    );
+}
+
+#[test]
+fn unparseable_capture_falls_through_to_404() {
+    assert_eq!(
+        routes().parse("/users/abc"),
+        Route::NotFound("/users/abc".to_string())
+    );
+    assert_eq!(
+        routes().parse("/users/7/extra"),
+        Route::NotFound("/users/7/extra".to_string())
+    );
+}
+
+#[test]
+fn nested_routes_and_fallback() {
+    let routes = routes();
+    assert_eq!(routes.parse("/admin"), Route::Admin(AdminRoute::Dashboard));
+    assert_eq!(
+        routes.parse("/admin/settings/"),
+        Route::Admin(AdminRoute::Settings)
+    );
+    assert_eq!(
+        routes.parse("/admin/nope/really"),
+        Route::Admin(AdminRoute::Unknown(vec![
+            "nope".to_string(),
+            "really".to_string()
+        ]))
+    );
+}
+
+#[test]
+fn formatting_round_trips() {
+    let url = Url::path(["users", "jane doe"]).query("tab", "a&b");
+    assert_eq!(url.to_string(), "/users/jane%20doe?tab=a%26b");
+    assert_eq!(Url::parse(&url.to_string()), url);
+    assert_eq!(Url::path(Vec::<String>::new()).to_string(), "/");
+    assert_eq!(
+        Url::parse("/a+b?q=a+b").segments,
+        vec!["a+b".to_string()],
+        "plus is literal in paths"
+    );
+    assert_eq!(Url::parse("/?q=a+b").query["q"], "a b");
+}
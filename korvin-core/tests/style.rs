@@ -0,0 +1,68 @@
+use korvin_core::style::Stylesheet;
+
+fn counter() -> Stylesheet {
+    Stylesheet::new("counter")
+        .rule("&", [("display", "flex")])
+        .nest("button, a", |nested| {
+            nested
+                .rule("&", [("padding", "0.5rem")])
+                .rule("&:hover", [("color", "red")])
+        })
+        .media("(max-width: 600px)", |small| {
+            small.rule("span", [("display", "none")])
+        })
+        .build()
+}
+
+#[test]
+fn selectors_are_scoped() {
+    let stylesheet = counter();
+    let scope = stylesheet.scope();
+    assert!(scope.starts_with("korvin-counter-"));
+    assert_eq!(
+        stylesheet.to_css(),
+        format!(
+            ".{scope} {{ display: flex; }}\n\
+             .{scope} button, .{scope} a {{ padding: 0.5rem; }}\n\
+             .{scope} button:hover, .{scope} a:hover {{ color: red; }}\n\
+             @media (max-width: 600px) {{\n\
+             .{scope} span {{ display: none; }}\n\
+             }}\n"
+        )
+    );
+}
+
+#[test]
+fn scope_depends_on_contents() {
+    assert_eq!(counter().scope(), counter().scope());
+    let other = Stylesheet::new("counter")
+        .rule("&", [("display", "grid")])
+        .build();
+    assert_ne!(counter().scope(), other.scope());
+}
+
+#[test]
+fn names_are_restricted_to_class_characters() {
+    let stylesheet = Stylesheet::new("my counter.v2")
+        .rule("&", [("display", "flex")])
+        .build();
+    let scope = stylesheet.scope();
+    assert!(scope.starts_with("korvin-my-counter-v2-"), "{scope}");
+    assert!(stylesheet.to_css().starts_with(&format!(".{scope} {{")));
+}
+
+#[test]
+fn only_top_level_commas_separate_selectors() {
+    let stylesheet = Stylesheet::new("list")
+        .rule(":is(a, b) span, &:not(.x, .y)", [("color", "red")])
+        .rule("[title=\"a, b\"]", [("color", "blue")])
+        .build();
+    let scope = stylesheet.scope();
+    assert_eq!(
+        stylesheet.to_css(),
+        format!(
+            ".{scope} :is(a, b) span, .{scope}:not(.x, .y) {{ color: red; }}\n\
+             .{scope} [title=\"a, b\"] {{ color: blue; }}\n"
+        )
+    );
+}
@@ -0,0 +1,207 @@
+mod common;
+
+use common::{executor, SLEEPS};
+use futures::{executor::LocalPool, stream, StreamExt};
+use korvin_core::flavors::elm_like::{
+    cmd::Executor,
+    sub::{RunningSubscriptions, Sub},
+};
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+/// What happened to the streams of subscriptions, by name.
+#[derive(Default, Clone)]
+struct Events(Rc<RefCell<Vec<String>>>);
+
+impl Events {
+    fn push(&self, event: String) {
+        self.0.borrow_mut().push(event);
+    }
+
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut self.0.borrow_mut())
+    }
+}
+
+struct Stopped(&'static str, Events);
+
+impl Drop for Stopped {
+    fn drop(&mut self) {
+        self.1.push(format!("stopped {}", self.0));
+    }
+}
+
+/// Sends `messages` and keeps running until stopped.
+fn tracked(name: &'static str, messages: Vec<u32>, events: &Events) -> Sub<u32> {
+    let events = events.clone();
+    Sub::stream(name, move || {
+        events.push(format!("started {name}"));
+        let stopped = Stopped(name, events);
+        stream::iter(messages)
+            .chain(stream::pending())
+            .map(move |message| {
+                let _ = &stopped;
+                message
+            })
+    })
+}
+
+fn sync<M: 'static>(
+    running: &mut RunningSubscriptions,
+    pool: &mut LocalPool,
+    executor: &Rc<dyn Executor>,
+    sub: Sub<M>,
+) -> Vec<M> {
+    let received = Rc::new(RefCell::new(vec![]));
+    let send = {
+        let received = received.clone();
+        move |message| received.borrow_mut().push(message)
+    };
+    running.sync(sub, executor, send);
+    pool.run_until_stalled();
+    received.take()
+}
+
+fn sorted(mut events: Vec<String>) -> Vec<String> {
+    events.sort();
+    events
+}
+
+#[test]
+fn new_subscriptions_are_started() {
+    let (mut pool, executor) = executor();
+    let executor: Rc<dyn Executor> = Rc::new(executor);
+    let events = Events::default();
+    let mut running = RunningSubscriptions::default();
+    let received = sync(
+        &mut running,
+        &mut pool,
+        &executor,
+        tracked("numbers", vec![1, 2], &events),
+    );
+    assert_eq!(received, vec![1, 2]);
+    assert_eq!(events.take(), vec!["started numbers"]);
+    assert_eq!(running.len(), 1);
+}
+
+#[test]
+fn unchanged_subscriptions_keep_running() {
+    let (mut pool, executor) = executor();
+    let executor: Rc<dyn Executor> = Rc::new(executor);
+    let events = Events::default();
+    let mut running = RunningSubscriptions::default();
+    sync(
+        &mut running,
+        &mut pool,
+        &executor,
+        tracked("numbers", vec![1], &events),
+    );
+    events.take();
+
+    let received = sync(
+        &mut running,
+        &mut pool,
+        &executor,
+        tracked("numbers", vec![1], &events),
+    );
+    assert_eq!(received, Vec::<u32>::new(), "not restarted");
+    assert_eq!(events.take(), Vec::<String>::new());
+    assert_eq!(running.len(), 1);
+}
+
+#[test]
+fn subscriptions_no_longer_returned_are_stopped() {
+    let (mut pool, executor) = executor();
+    let executor: Rc<dyn Executor> = Rc::new(executor);
+    let events = Events::default();
+    let mut running = RunningSubscriptions::default();
+    sync(
+        &mut running,
+        &mut pool,
+        &executor,
+        Sub::batch([tracked("a", vec![], &events), tracked("b", vec![], &events)]),
+    );
+    events.take();
+
+    sync(
+        &mut running,
+        &mut pool,
+        &executor,
+        tracked("b", vec![], &events),
+    );
+    assert_eq!(events.take(), vec!["stopped a"]);
+
+    sync(&mut running, &mut pool, &executor, Sub::<u32>::none());
+    assert_eq!(events.take(), vec!["stopped b"]);
+    assert!(running.is_empty());
+}
+
+#[test]
+fn rekeyed_subscriptions_are_restarted() {
+    let (mut pool, executor) = executor();
+    let executor: Rc<dyn Executor> = Rc::new(executor);
+    let events = Events::default();
+    let mut running = RunningSubscriptions::default();
+    sync(
+        &mut running,
+        &mut pool,
+        &executor,
+        tracked("ticks", vec![], &events).key(1),
+    );
+    events.take();
+
+    sync(
+        &mut running,
+        &mut pool,
+        &executor,
+        tracked("ticks", vec![], &events).key(2),
+    );
+    assert_eq!(
+        sorted(events.take()),
+        vec!["started ticks", "stopped ticks"]
+    );
+    assert_eq!(running.len(), 1);
+}
+
+#[derive(Debug, PartialEq)]
+enum Message {
+    Left(u32),
+    Right(u32),
+}
+
+#[test]
+fn the_same_subscription_mapped_by_two_children_runs_twice() {
+    let (mut pool, executor) = executor();
+    let executor: Rc<dyn Executor> = Rc::new(executor);
+    let events = Events::default();
+    let mut running = RunningSubscriptions::default();
+    let sub = || {
+        Sub::batch([
+            tracked("shared", vec![1], &events).map(Message::Left),
+            tracked("shared", vec![2], &events).map(Message::Right),
+        ])
+    };
+    let received = sync(&mut running, &mut pool, &executor, sub());
+    assert_eq!(received, vec![Message::Left(1), Message::Right(2)]);
+    assert_eq!(running.len(), 2);
+
+    events.take();
+    sync(&mut running, &mut pool, &executor, sub());
+    assert_eq!(events.take(), Vec::<String>::new(), "both kept running");
+}
+
+#[test]
+fn intervals_sleep_on_the_given_executor() {
+    let (mut pool, executor) = executor();
+    let slept = executor.slept.clone();
+    let executor: Rc<dyn Executor> = Rc::new(executor);
+    let mut running = RunningSubscriptions::default();
+    let every = Duration::from_secs(2);
+    let received = sync(
+        &mut running,
+        &mut pool,
+        &executor,
+        Sub::interval(every, || "tick"),
+    );
+    assert_eq!(received, vec!["tick"; SLEEPS]);
+    assert_eq!(*slept.borrow(), vec![every; SLEEPS]);
+}
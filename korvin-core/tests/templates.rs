@@ -0,0 +1,19 @@
+use korvin_core::element_builder::{AsElementBuilder, ChildRecipe};
+
+#[test]
+fn only_marked_subtrees_are_templates() {
+    let recipe = "ul"
+        .child("li".template().child("span".text("static")))
+        .child("li")
+        .build();
+    assert!(!recipe.template);
+    let templates = recipe
+        .children
+        .iter()
+        .map(|child| match child {
+            ChildRecipe::Element(child) => child.template,
+            ChildRecipe::Memo(_) => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(templates, vec![true, false]);
+}
@@ -0,0 +1,97 @@
+use korvin_core::{
+    element_builder::{AsElementBuilder, ElementBuilder},
+    html::{self, kind, ButtonType, Element, InputType, Kind, Target, ToAttribute},
+};
+
+fn recipe(builder: impl Into<ElementBuilder>) -> String {
+    format!("{:?}", builder.into().build())
+}
+
+#[test]
+fn typed_attributes_lower_to_plain_ones() {
+    assert_eq!(
+        recipe(
+            html::input()
+                .id("slider")
+                .r#type(InputType::Range)
+                .min(0)
+                .max(60)
+                .step(0.5)
+                .input_value("30")
+        ),
+        recipe(
+            "input"
+                .attribute("id", "slider")
+                .attribute("type", "range")
+                .attribute("min", "0")
+                .attribute("max", "60")
+                .attribute("step", "0.5")
+                .input_value("30")
+        ),
+    );
+}
+
+#[test]
+fn boolean_and_true_false_attributes() {
+    assert_eq!(
+        recipe(
+            html::button()
+                .r#type(ButtonType::Submit)
+                .disabled(true)
+                .hidden(false)
+                .draggable(false)
+                .text("save")
+        ),
+        recipe(
+            "button"
+                .attribute("type", "submit")
+                .attribute("disabled", "")
+                .attribute("draggable", "false")
+                .text("save")
+        ),
+    );
+}
+
+#[test]
+fn false_and_none_remove_earlier_values() {
+    assert_eq!(
+        recipe(html::button().disabled(true).disabled(false)),
+        recipe("button"),
+    );
+    let titled = "button".attribute("title", "save");
+    assert_eq!(recipe(None::<&str>.set(titled, "title")), recipe("button"));
+}
+
+#[test]
+fn children_data_and_aria() {
+    assert_eq!(
+        recipe(
+            html::label()
+                .r#for("name")
+                .data("field", "name")
+                .aria("live", "polite")
+                .child(html::a().href("/help").target(Target::Blank).text("?"))
+        ),
+        recipe(
+            "label"
+                .attribute("for", "name")
+                .attribute("data-field", "name")
+                .attribute("aria-live", "polite")
+                .child(
+                    "a".attribute("href", "/help")
+                        .attribute("target", "_blank")
+                        .text("?")
+                )
+        ),
+    );
+}
+
+#[test]
+fn tags() {
+    fn tag<K: Kind>(_: Element<K>) -> &'static str {
+        K::TAG
+    }
+    assert_eq!(tag(html::h1()), "h1");
+    assert_eq!(tag(html::option()), "option");
+    assert_eq!(kind::Textarea::TAG, "textarea");
+}
@@ -0,0 +1,49 @@
+use korvin_core::element_builder::value_cache::ValueCache;
+
+#[test]
+fn values_are_matched_by_what_they_were_built_from() {
+    let mut cache = ValueCache::with_capacity(16);
+    assert_eq!(cache.cached("1").as_ref(), "1");
+    assert_eq!(cache.cached(1_u8).as_ref(), "1");
+    assert_eq!(cache.cached(1.0_f64).as_ref(), "1");
+    assert_eq!(cache.cached('1').as_ref(), "1");
+    assert_eq!(cache.cached(1.5_f32).as_ref(), "1.5");
+    assert_eq!(cache.len(), 5);
+
+    assert_eq!(cache.cached(String::from("1")).as_ref(), "1");
+    assert_eq!(cache.cached(1_usize).as_ref(), "1");
+    assert_eq!(cache.len(), 5);
+}
+
+#[test]
+fn least_recently_used_values_are_evicted() {
+    let mut cache = ValueCache::with_capacity(2);
+    cache.cached("old");
+    cache.next_rebuild();
+    cache.cached("kept");
+    cache.next_rebuild();
+    cache.cached("kept");
+    cache.cached("new");
+    assert_eq!(cache.len(), 3, "evicting only happens between rebuilds");
+
+    cache.next_rebuild();
+    assert_eq!(cache.len(), 2);
+    cache.cached("kept");
+    cache.cached("new");
+    assert_eq!(cache.len(), 2, "recently used values were evicted");
+}
+
+#[test]
+fn a_rebuild_larger_than_capacity_only_evicts_the_excess() {
+    let mut cache = ValueCache::with_capacity(2);
+    cache.cached("old");
+    cache.next_rebuild();
+    (0..5_u32).for_each(|value| {
+        cache.cached(value);
+    });
+    cache.next_rebuild();
+    assert_eq!(cache.len(), 2);
+
+    cache.next_rebuild();
+    assert_eq!(cache.len(), 2, "nothing to evict within capacity");
+}
@@ -0,0 +1,84 @@
+use korvin_core::element_builder::{AsElementBuilder, ElementBuilder};
+use std::borrow::Cow;
+
+fn recipe(builder: ElementBuilder) -> String {
+    format!("{:?}", builder.build())
+}
+
+#[test]
+fn owned_strings_match_borrowed_ones() {
+    let title = String::from("title");
+    assert_eq!(
+        recipe(
+            "div"
+                .text(String::from("text"))
+                .attribute("title", &title)
+                .attribute("lang", Cow::Borrowed("en"))
+                .style("color", Box::<str>::from("red"))
+        ),
+        recipe(
+            "div"
+                .text("text")
+                .attribute("title", "title")
+                .attribute("lang", "en")
+                .style("color", "red")
+        ),
+    );
+}
+
+#[test]
+fn numbers_are_formatted() {
+    assert_eq!(
+        recipe(
+            "input"
+                .input_value(42_u8)
+                .attribute("min", -3_i64)
+                .attribute("step", 0.5_f64)
+                .style("order", 2_usize)
+        ),
+        recipe(
+            "input"
+                .input_value("42")
+                .attribute("min", "-3")
+                .attribute("step", "0.5")
+                .style("order", "2")
+        ),
+    );
+}
+
+#[test]
+fn false_and_none_leave_values_out() {
+    assert_eq!(
+        recipe(
+            "button"
+                .attribute("disabled", true)
+                .attribute("hidden", false)
+                .attribute("title", Some("save"))
+                .attribute("lang", None::<&str>)
+                .style("color", None::<String>)
+                .text(Some(1))
+        ),
+        recipe(
+            "button"
+                .attribute("disabled", "")
+                .attribute("title", "save")
+                .text("1")
+        ),
+    );
+}
+
+#[test]
+fn later_absent_values_remove_earlier_ones() {
+    assert_eq!(
+        recipe(
+            "input"
+                .attribute("required", true)
+                .attribute("required", false)
+                .style("color", "red")
+                .style("color", None::<&str>)
+                .input_value("draft")
+                .input_value(None::<&str>)
+        ),
+        recipe("input".into_builder()),
+    );
+}
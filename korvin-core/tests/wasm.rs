@@ -495,3 +495,1078 @@ pub mod optional_sibling {
         Ok(())
     }
 }
+pub mod async_event_aborted_on_unmount {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+    use wasm_bindgen::JsCast;
+    use web_sys::{AbortSignal, HtmlElement, MouseEvent};
+
+    #[wasm_bindgen_test]
+    pub async fn test_task_is_aborted_when_element_is_removed() -> Result<()> {
+        let mut runtime = runtime!()?;
+        let signal: Rc<RefCell<Option<AbortSignal>>> = Default::default();
+        let app = |with_button: bool| {
+            let signal = signal.clone();
+            "main"
+                .children(with_button.then(move || {
+                    "button".event_async((), "click", move |_: MouseEvent, task_signal| {
+                        signal.borrow_mut().replace(task_signal);
+                        Timeout::new(std::time::Duration::from_secs(60))
+                    })
+                }))
+                .build()
+        };
+        runtime.dom_executor.rebuild(app(true))?;
+        runtime
+            .root_element()
+            .as_ref()
+            .query_selector("button")
+            .map_err(|e| eyre!("{e:?}"))?
+            .ok_or_else(|| eyre!("no button"))?
+            .unchecked_into::<HtmlElement>()
+            .click();
+        let task_signal = signal
+            .borrow()
+            .clone()
+            .ok_or_else(|| eyre!("task not spawned"))?;
+        (!task_signal.aborted())
+            .then_some(())
+            .ok_or_else(|| eyre!("task aborted too early"))?;
+        runtime.dom_executor.rebuild(app(false))?;
+        task_signal
+            .aborted()
+            .then_some(())
+            .ok_or_else(|| eyre!("task was not aborted after its element got removed"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+pub mod leak_accounting {
+    use super::*;
+    use web_sys::MouseEvent;
+
+    #[wasm_bindgen_test]
+    pub async fn test_closures_are_dropped_with_their_elements() -> Result<()> {
+        let mut runtime = runtime!()?.with_leak_check(true);
+        let app = |buttons: usize| {
+            "main"
+                .children(
+                    (0..buttons).map(|i| "button".key(i).event(i, "click", |_: MouseEvent| {})),
+                )
+                .build()
+        };
+        runtime.dom_executor.rebuild(app(0))?;
+        let before = korvin_core::stats::thread();
+        runtime.dom_executor.rebuild(app(3))?;
+        let during = korvin_core::stats::thread();
+        (during.live_closures == before.live_closures + 3
+            && during.listener_registrations == before.listener_registrations + 3
+            && during.live_elements == before.live_elements + 3)
+            .then_some(())
+            .ok_or_else(|| eyre!("unexpected stats: {before:?} -> {during:?}"))?;
+        runtime.dom_executor.rebuild(app(0))?;
+        let after = korvin_core::stats::thread();
+        (after.live_closures == before.live_closures
+            && after.listener_registrations == before.listener_registrations
+            && after.live_elements == before.live_elements)
+            .then_some(())
+            .ok_or_else(|| eyre!("resources leaked: {before:?} -> {after:?}"))?;
+        runtime
+            .leaked_closures()
+            .is_empty()
+            .then_some(())
+            .ok_or_else(|| eyre!("leaked closures: {:?}", runtime.leaked_closures()))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+pub mod html_map {
+    use super::*;
+    use futures::{FutureExt, StreamExt};
+    use korvin_core::flavors::elm_like::{html::Html, Communicator};
+    use wasm_bindgen::JsCast;
+    use web_sys::{HtmlElement, MouseEvent};
+
+    #[derive(Debug, PartialEq)]
+    enum ChildMessage {
+        Clicked,
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum ParentMessage {
+        Child(usize, ChildMessage),
+    }
+
+    fn child() -> Html<ChildMessage> {
+        Html::from("button").on((), "click", |_: MouseEvent| ChildMessage::Clicked)
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_child_messages_are_lifted_into_parent() -> Result<()> {
+        let mut runtime = runtime!()?;
+        let (mut rx, communicator) = Communicator::<ParentMessage>::create();
+        let app = Html::from("main").children((0..2).map(|index| {
+            child()
+                .key(index)
+                .map(move |message| ParentMessage::Child(index, message))
+        }));
+        runtime
+            .dom_executor
+            .rebuild(app.render(&communicator).build())?;
+        runtime
+            .root_element()
+            .as_ref()
+            .query_selector("button:last-child")
+            .map_err(|e| eyre!("{e:?}"))?
+            .ok_or_else(|| eyre!("no button"))?
+            .unchecked_into::<HtmlElement>()
+            .click();
+        let message = rx.next().now_or_never().flatten();
+        (message == Some(ParentMessage::Child(1, ChildMessage::Clicked)))
+            .then_some(())
+            .ok_or_else(|| eyre!("unexpected message: {message:?}"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+pub mod program_debugger {
+    use super::*;
+    use korvin_core::flavors::elm_like::{cmd::Cmd, html::Html, program::Program};
+    use wasm_bindgen::JsCast;
+    use web_sys::{HtmlElement, MouseEvent};
+
+    fn click(selector: &str) -> Result<()> {
+        web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| eyre!("no document"))?
+            .query_selector(selector)
+            .map_err(|e| eyre!("{e:?}"))?
+            .ok_or_else(|| eyre!("nothing matches {selector}"))?
+            .unchecked_into::<HtmlElement>()
+            .click();
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_jumping_back_shows_historical_model() -> Result<()> {
+        let runtime = runtime!()?;
+        let root = runtime.root_element().as_ref().clone();
+        let handle = Program::html(
+            || 0,
+            |count: &mut i32, ()| {
+                *count += 1;
+                Cmd::none()
+            },
+            |count: &i32| {
+                Html::from("button")
+                    .attribute("id", "debugged")
+                    .text(*count)
+                    .on((), "click", |_: MouseEvent| ())
+            },
+        )
+        .debugger()
+        .mount_to(root.clone());
+        wait_for_logs().await;
+        click("#debugged")?;
+        click("#debugged")?;
+        wait_for_logs().await;
+        let text = || root.text_content().unwrap_or_default();
+        (text() == "2")
+            .then_some(())
+            .ok_or_else(|| eyre!("expected live count 2, got {}", text()))?;
+        click(".korvin-debugger li:first-child")?;
+        wait_for_logs().await;
+        (text() == "0")
+            .then_some(())
+            .ok_or_else(|| eyre!("expected initial count 0, got {}", text()))?;
+        handle.stop();
+        wait_for_logs().await;
+        web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or_else(|| eyre!("no document"))?
+            .query_selector(".korvin-debugger")
+            .map_err(|e| eyre!("{e:?}"))?
+            .is_none()
+            .then_some(())
+            .ok_or_else(|| eyre!("overlay still mounted after stop"))?;
+        Ok(())
+    }
+}
+
+pub mod form_controls {
+    use super::*;
+    use wasm_bindgen::JsCast;
+    use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+
+    fn app(text: &str, selected: &[&str], checked: bool) -> ElementBuilder {
+        "form"
+            .child("textarea".input_value(text))
+            .child(
+                "select"
+                    .attribute("multiple", "multiple")
+                    .children(["a", "b", "c"].map(|value| "option".attribute("value", value)))
+                    .selected_values(selected.iter().copied()),
+            )
+            .child("input".attribute("type", "checkbox").checked(checked))
+    }
+
+    fn find<T: JsCast>(runtime: &Runtime, selector: &str) -> Result<T> {
+        runtime
+            .root_element()
+            .as_ref()
+            .query_selector(selector)
+            .map_err(|e| eyre!("{e:?}"))?
+            .ok_or_else(|| eyre!("nothing matches {selector}"))?
+            .dyn_into::<T>()
+            .map_err(|e| eyre!("{selector} has a wrong type: {e:?}"))
+    }
+
+    fn selected(select: &HtmlSelectElement) -> Vec<String> {
+        korvin_core::raw_operations::selected_values(select)
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_controls_follow_the_view() -> Result<()> {
+        let mut runtime = runtime!()?;
+        runtime
+            .dom_executor
+            .rebuild(app("one", &[], false).build())?;
+        runtime
+            .dom_executor
+            .rebuild(app("two", &["a", "c"], true).build())?;
+        let textarea = find::<HtmlTextAreaElement>(&runtime, "textarea")?;
+        let select = find::<HtmlSelectElement>(&runtime, "select")?;
+        let checkbox = find::<HtmlInputElement>(&runtime, "input")?;
+        (textarea.value() == "two")
+            .then_some(())
+            .ok_or_else(|| eyre!("unexpected textarea value: {}", textarea.value()))?;
+        (selected(&select) == ["a", "c"])
+            .then_some(())
+            .ok_or_else(|| eyre!("unexpected selection: {:?}", selected(&select)))?;
+        checkbox
+            .checked()
+            .then_some(())
+            .ok_or_else(|| eyre!("checkbox is not checked"))?;
+
+        runtime
+            .dom_executor
+            .rebuild(app("two", &["b"], false).build())?;
+        (selected(&select) == ["b"])
+            .then_some(())
+            .ok_or_else(|| eyre!("unexpected selection: {:?}", selected(&select)))?;
+        (!checkbox.checked())
+            .then_some(())
+            .ok_or_else(|| eyre!("checkbox is still checked"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+
+pub mod controlled_inputs {
+    use super::*;
+    use std::{cell::Cell, rc::Rc};
+    use wasm_bindgen::JsCast;
+    use web_sys::HtmlInputElement;
+
+    fn app(value: &str, checked: bool) -> ElementBuilder {
+        "form"
+            .child("input".attribute("id", "text").input_value(value))
+            .child(
+                "input"
+                    .attribute("id", "check")
+                    .attribute("type", "checkbox")
+                    .checked(checked),
+            )
+    }
+
+    fn find(runtime: &Runtime, id: &str) -> Result<HtmlInputElement> {
+        runtime
+            .root_element()
+            .as_ref()
+            .query_selector(&format!("#{id}"))
+            .map_err(|e| eyre!("{e:?}"))?
+            .ok_or_else(|| eyre!("no #{id}"))?
+            .dyn_into::<HtmlInputElement>()
+            .map_err(|e| eyre!("#{id} is not an input: {e:?}"))
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_rejected_edits_are_reverted() -> Result<()> {
+        let mut runtime = runtime!()?;
+        runtime.dom_executor.rebuild(app("ABC", false).build())?;
+        let text = find(&runtime, "text")?;
+        let check = find(&runtime, "check")?;
+        text.focus().map_err(|e| eyre!("{e:?}"))?;
+        text.set_value("abcd");
+        text.set_selection_range(2, 2).map_err(|e| eyre!("{e:?}"))?;
+        check.click();
+
+        runtime.dom_executor.rebuild(app("ABC", false).build())?;
+        (text.value() == "ABC")
+            .then_some(())
+            .ok_or_else(|| eyre!("value was not reverted: {}", text.value()))?;
+        (!check.checked())
+            .then_some(())
+            .ok_or_else(|| eyre!("checkbox was not reverted"))?;
+
+        text.set_value("AxBC");
+        text.set_selection_range(2, 2).map_err(|e| eyre!("{e:?}"))?;
+        runtime.dom_executor.rebuild(app("ABC", false).build())?;
+        let caret = text.selection_start().map_err(|e| eyre!("{e:?}"))?;
+        (caret == Some(2))
+            .then_some(())
+            .ok_or_else(|| eyre!("caret moved to {caret:?}"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_normalised_values_keep_caret_and_composition() -> Result<()> {
+        let mut runtime = runtime!()?;
+        runtime.dom_executor.rebuild(app("", false).build())?;
+        let text = find(&runtime, "text")?;
+        text.focus().map_err(|e| eyre!("{e:?}"))?;
+        text.set_value("abc");
+        text.set_selection_range(2, 2).map_err(|e| eyre!("{e:?}"))?;
+
+        // the model upper-cases whatever was typed
+        runtime.dom_executor.rebuild(app("ABC", false).build())?;
+        (text.value() == "ABC")
+            .then_some(())
+            .ok_or_else(|| eyre!("value was not normalised: {}", text.value()))?;
+        let caret = text.selection_start().map_err(|e| eyre!("{e:?}"))?;
+        (caret == Some(2))
+            .then_some(())
+            .ok_or_else(|| eyre!("caret moved to {caret:?}"))?;
+
+        let composition = |name: &str| -> Result<()> {
+            let event = web_sys::CompositionEvent::new(name).map_err(|e| eyre!("{e:?}"))?;
+            text.dispatch_event(&event).map_err(|e| eyre!("{e:?}"))?;
+            Ok(())
+        };
+        composition("compositionstart")?;
+        text.set_value("ABCd");
+        runtime.dom_executor.rebuild(app("ABCD", false).build())?;
+        (text.value() == "ABCd")
+            .then_some(())
+            .ok_or_else(|| eyre!("composition was interrupted: {}", text.value()))?;
+        // the value deferred during the composition is applied when it ends, without a rebuild
+        composition("compositionend")?;
+        (text.value() == "ABCD").then_some(()).ok_or_else(|| {
+            eyre!(
+                "value was not normalised after composition: {}",
+                text.value()
+            )
+        })?;
+        wait_for_logs().await;
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_input_listeners_run_once_per_composition() -> Result<()> {
+        let mut runtime = runtime!()?;
+        let inputs = Rc::new(Cell::new(0));
+        let app = |value: &str| {
+            let inputs = inputs.clone();
+            "form".child("input".attribute("id", "text").input_value(value).event(
+                (),
+                "input",
+                move |_: web_sys::InputEvent| inputs.set(inputs.get() + 1),
+            ))
+        };
+        runtime.dom_executor.rebuild(app("").build())?;
+        let text = find(&runtime, "text")?;
+        text.focus().map_err(|e| eyre!("{e:?}"))?;
+
+        let dispatch = |event: web_sys::Event| -> Result<()> {
+            text.dispatch_event(&event).map_err(|e| eyre!("{e:?}"))?;
+            Ok(())
+        };
+        let composition = |name: &str| -> Result<web_sys::Event> {
+            web_sys::CompositionEvent::new(name)
+                .map(Into::into)
+                .map_err(|e| eyre!("{e:?}"))
+        };
+        dispatch(composition("compositionstart")?)?;
+        text.set_value("a");
+        dispatch(
+            web_sys::InputEvent::new("input")
+                .map(Into::into)
+                .map_err(|e| eyre!("{e:?}"))?,
+        )?;
+        runtime.dom_executor.rebuild(app("A").build())?;
+        dispatch(composition("compositionend")?)?;
+        (inputs.get() == 1)
+            .then_some(())
+            .ok_or_else(|| eyre!("input listener ran {} times", inputs.get()))?;
+        (text.value() == "A")
+            .then_some(())
+            .ok_or_else(|| eyre!("value was not resynced: {}", text.value()))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+
+pub mod editable_controls {
+    use super::*;
+    use futures::{FutureExt, StreamExt};
+    use korvin_core::flavors::elm_like::{editable, Communicator};
+    use wasm_bindgen::JsCast;
+    use web_sys::{HtmlElement, InputEvent};
+
+    #[derive(Debug, PartialEq)]
+    enum Message {
+        Edited(String),
+    }
+
+    fn app(communicator: &Communicator<Message>, text: &str) -> ElementBuilder {
+        "main".child(editable((), communicator, text, Message::Edited))
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_editable_text_is_controlled() -> Result<()> {
+        let mut runtime = runtime!()?;
+        let (mut rx, communicator) = Communicator::<Message>::create();
+        runtime
+            .dom_executor
+            .rebuild(app(&communicator, "first").build())?;
+        let element = runtime
+            .root_element()
+            .as_ref()
+            .query_selector("[contenteditable]")
+            .map_err(|e| eyre!("{e:?}"))?
+            .ok_or_else(|| eyre!("no editable element"))?
+            .unchecked_into::<HtmlElement>();
+        let text = || element.text_content().unwrap_or_default();
+        (text() == "first")
+            .then_some(())
+            .ok_or_else(|| eyre!("unexpected text: {}", text()))?;
+
+        element.set_text_content(Some("typed"));
+        let event = InputEvent::new("input").map_err(|e| eyre!("{e:?}"))?;
+        element.dispatch_event(&event).map_err(|e| eyre!("{e:?}"))?;
+        let message = rx.next().now_or_never().flatten();
+        (message == Some(Message::Edited("typed".to_string())))
+            .then_some(())
+            .ok_or_else(|| eyre!("unexpected message: {message:?}"))?;
+
+        runtime
+            .dom_executor
+            .rebuild(app(&communicator, "second").build())?;
+        (text() == "second")
+            .then_some(())
+            .ok_or_else(|| eyre!("text was not updated: {}", text()))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+
+pub mod element_properties {
+    use super::*;
+    use korvin_core::data::PropertyValue;
+    use wasm_bindgen::{JsCast, JsValue};
+    use web_sys::HtmlInputElement;
+
+    fn app(indeterminate: Option<bool>, config: Option<u32>) -> ElementBuilder {
+        let checkbox = "input".attribute("type", "checkbox");
+        let checkbox = match indeterminate {
+            Some(indeterminate) => checkbox.property("indeterminate", indeterminate),
+            None => checkbox,
+        };
+        match config {
+            Some(version) => {
+                let object = js_sys::Object::new();
+                js_sys::Reflect::set(&object, &"version".into(), &version.into()).unwrap();
+                checkbox.property("config", PropertyValue::keyed(version, object))
+            }
+            None => checkbox,
+        }
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_properties_are_set_and_restored() -> Result<()> {
+        let mut runtime = runtime!()?;
+        runtime
+            .dom_executor
+            .rebuild(app(Some(true), Some(1)).build())?;
+        let checkbox = runtime
+            .root_element()
+            .as_ref()
+            .query_selector("input")
+            .map_err(|e| eyre!("{e:?}"))?
+            .ok_or_else(|| eyre!("no checkbox"))?
+            .unchecked_into::<HtmlInputElement>();
+        let version = || {
+            js_sys::Reflect::get(&checkbox, &"config".into())
+                .and_then(|config| js_sys::Reflect::get(&config, &"version".into()))
+                .ok()
+                .and_then(|version| version.as_f64())
+        };
+        checkbox
+            .indeterminate()
+            .then_some(())
+            .ok_or_else(|| eyre!("indeterminate was not set"))?;
+        (version() == Some(1.))
+            .then_some(())
+            .ok_or_else(|| eyre!("unexpected config version {:?}", version()))?;
+
+        runtime
+            .dom_executor
+            .rebuild(app(Some(true), Some(2)).build())?;
+        (version() == Some(2.))
+            .then_some(())
+            .ok_or_else(|| eyre!("config was not replaced: {:?}", version()))?;
+
+        runtime.dom_executor.rebuild(app(None, None).build())?;
+        (!checkbox.indeterminate())
+            .then_some(())
+            .ok_or_else(|| eyre!("indeterminate was not restored"))?;
+        js_sys::Reflect::get(&checkbox, &"config".into())
+            .map_err(|e| eyre!("{e:?}"))?
+            .is_undefined()
+            .then_some(())
+            .ok_or_else(|| eyre!("config was not restored"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_unchanged_objects_are_not_set_again() -> Result<()> {
+        let mut runtime = runtime!()?;
+        let app = |stream: &js_sys::Object| "video".property("srcObject", JsValue::from(stream));
+        let first = js_sys::Object::new();
+        runtime.dom_executor.rebuild(app(&first).build())?;
+        let video = runtime
+            .root_element()
+            .as_ref()
+            .query_selector("video")
+            .map_err(|e| eyre!("{e:?}"))?
+            .ok_or_else(|| eyre!("no video"))?;
+        // counts what the runtime sets from now on
+        js_sys::Function::new_with_args(
+            "element",
+            "let value = element.srcObject; element.sets = 0; \
+             Object.defineProperty(element, 'srcObject', { \
+                 get: () => value, \
+                 set: (next) => { element.sets += 1; value = next; }, \
+             });",
+        )
+        .call1(&JsValue::NULL, &video)
+        .map_err(|e| eyre!("{e:?}"))?;
+        let sets = || {
+            js_sys::Reflect::get(&video, &"sets".into())
+                .ok()
+                .and_then(|sets| sets.as_f64())
+        };
+
+        runtime.dom_executor.rebuild(app(&first).build())?;
+        (sets() == Some(0.))
+            .then_some(())
+            .ok_or_else(|| eyre!("unchanged object was set {:?} times", sets()))?;
+
+        runtime
+            .dom_executor
+            .rebuild(app(&js_sys::Object::new()).build())?;
+        (sets() == Some(1.))
+            .then_some(())
+            .ok_or_else(|| eyre!("new object was set {:?} times", sets()))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+
+pub mod class_and_style_diffing {
+    use super::*;
+    use wasm_bindgen::JsCast;
+    use web_sys::HtmlElement;
+
+    fn app(highlighted: bool) -> ElementBuilder {
+        let element = "div"
+            .attribute("id", "styled")
+            .class("base")
+            .class_if("highlighted", highlighted);
+        match highlighted {
+            true => element.class("external").style("color", "red"),
+            false => element,
+        }
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_only_korvin_classes_and_styles_are_reverted() -> Result<()> {
+        let mut runtime = runtime!()?;
+        runtime.dom_executor.rebuild(app(false).build())?;
+        let element = runtime
+            .root_element()
+            .as_ref()
+            .query_selector("#styled")
+            .map_err(|e| eyre!("{e:?}"))?
+            .ok_or_else(|| eyre!("no element"))?
+            .unchecked_into::<HtmlElement>();
+        let classes = element.class_list();
+        classes
+            .add_2("external", "transition")
+            .map_err(|e| eyre!("{e:?}"))?;
+
+        runtime.dom_executor.rebuild(app(true).build())?;
+        let class_name = || element.class_name();
+        (classes.contains("highlighted") && classes.contains("transition"))
+            .then_some(())
+            .ok_or_else(|| eyre!("unexpected classes: {}", class_name()))?;
+        let color = || {
+            element
+                .style()
+                .get_property_value("color")
+                .unwrap_or_default()
+        };
+        (color() == "red")
+            .then_some(())
+            .ok_or_else(|| eyre!("unexpected color: {}", color()))?;
+
+        runtime.dom_executor.rebuild(app(false).build())?;
+        (classes.contains("base")
+            && !classes.contains("highlighted")
+            && classes.contains("external")
+            && classes.contains("transition"))
+        .then_some(())
+        .ok_or_else(|| eyre!("unexpected classes: {}", class_name()))?;
+        color()
+            .is_empty()
+            .then_some(())
+            .ok_or_else(|| eyre!("color was not removed: {}", color()))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+
+pub mod scoped_stylesheets {
+    use super::*;
+    use korvin_core::style::{self, Stylesheet};
+
+    fn injected(stylesheet: &Stylesheet) -> Result<bool> {
+        document()?
+            .query_selector(&format!(
+                "head style[data-korvin-scope=\"{}\"]",
+                stylesheet.scope()
+            ))
+            .map_err(|e| eyre!("{e:?}"))
+            .map(|style| style.is_some())
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_stylesheet_is_injected_while_used() -> Result<()> {
+        let mut runtime = runtime!()?;
+        let stylesheet = Stylesheet::new("scoped-test")
+            .rule("&", [("color", "red")])
+            .build();
+        let app = |count: usize| "main".children((0..count).map(|_| "p".stylesheet(&stylesheet)));
+
+        runtime.dom_executor.rebuild(app(2).build())?;
+        (injected(&stylesheet)? && style::users(&stylesheet) == 2)
+            .then_some(())
+            .ok_or_else(|| eyre!("stylesheet was not injected"))?;
+        runtime
+            .test()
+            .assert_contains_html(&format!("<p class=\"{}\">", stylesheet.scope()))?;
+
+        runtime.dom_executor.rebuild(app(1).build())?;
+        (injected(&stylesheet)? && style::users(&stylesheet) == 1)
+            .then_some(())
+            .ok_or_else(|| eyre!("stylesheet was removed while still used"))?;
+
+        runtime.dom_executor.rebuild(app(0).build())?;
+        (!injected(&stylesheet)? && style::users(&stylesheet) == 0)
+            .then_some(())
+            .ok_or_else(|| eyre!("stylesheet was not removed"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+
+pub mod memoized_components {
+    use super::*;
+    use korvin_core::component::Component;
+    use std::cell::Cell;
+
+    thread_local! {
+        static RENDERS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct Item;
+
+    impl Component for Item {
+        type Props = (u32, String);
+
+        fn render((id, label): &Self::Props) -> ElementBuilder {
+            RENDERS.with(|renders| renders.set(renders.get() + 1));
+            "li".attribute("data-id", id.to_string().as_str())
+                .text(label.as_str())
+        }
+    }
+
+    fn renders() -> usize {
+        RENDERS.with(|renders| renders.replace(0))
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_equal_props_skip_rendering() -> Result<()> {
+        let mut runtime = runtime!()?;
+        let app = |items: &[(u32, &str)]| {
+            "ul".children(
+                items
+                    .iter()
+                    .map(|(id, label)| Item::memo((*id, label.to_string())).key(*id)),
+            )
+        };
+
+        runtime
+            .dom_executor
+            .rebuild(app(&[(1, "one"), (2, "two")]).build())?;
+        (renders() == 2)
+            .then_some(())
+            .ok_or_else(|| eyre!("both items should render on the first rebuild"))?;
+        let first = runtime
+            .test()
+            .root_element()
+            .query_selector("li[data-id=\"1\"]")
+            .map_err(|e| eyre!("{e:?}"))?
+            .ok_or_else(|| eyre!("item was not rendered"))?;
+
+        runtime
+            .dom_executor
+            .rebuild(app(&[(2, "two"), (1, "one"), (3, "three")]).build())?;
+        (renders() == 1)
+            .then_some(())
+            .ok_or_else(|| eyre!("only the new item should render"))?;
+        runtime.test().assert_contains_html(
+            "<ul><li data-id=\"2\">two</li><li data-id=\"1\">one</li><li data-id=\"3\">three</li></ul>",
+        )?;
+        runtime
+            .test()
+            .root_element()
+            .query_selector("li[data-id=\"1\"]")
+            .map_err(|e| eyre!("{e:?}"))?
+            .is_some_and(|element| element == first)
+            .then_some(())
+            .ok_or_else(|| eyre!("reused item was recreated"))?;
+
+        runtime
+            .dom_executor
+            .rebuild(app(&[(2, "two"), (1, "uno")]).build())?;
+        (renders() == 1)
+            .then_some(())
+            .ok_or_else(|| eyre!("only the changed item should render"))?;
+        runtime.test().assert_contains_html(
+            "<ul><li data-id=\"2\">two</li><li data-id=\"1\">uno</li></ul>",
+        )?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+
+pub mod keyed_children {
+    use super::*;
+
+    #[wasm_bindgen_test]
+    pub async fn test_reordered_items_keep_their_elements() -> Result<()> {
+        let mut runtime = runtime!()?;
+        let app = |items: &[(u32, &str)]| {
+            "ul".keyed_children(
+                items.iter(),
+                |(id, _)| *id,
+                |(id, label)| "li".attribute("data-id", *id).text(*label),
+            )
+        };
+        let item = |runtime: &Runtime, id: u32| {
+            runtime
+                .test()
+                .root_element()
+                .query_selector(&format!("li[data-id=\"{id}\"]"))
+                .map_err(|e| eyre!("{e:?}"))?
+                .ok_or_else(|| eyre!("item {id} was not rendered"))
+        };
+
+        runtime
+            .dom_executor
+            .rebuild(app(&[(1, "one"), (2, "two")]).build())?;
+        let first = item(&runtime, 1)?;
+        let second = item(&runtime, 2)?;
+
+        runtime
+            .dom_executor
+            .rebuild(app(&[(3, "three"), (2, "two"), (1, "one")]).build())?;
+        runtime.test().assert_contains_html(
+            "<ul><li data-id=\"3\">three</li><li data-id=\"2\">two</li><li data-id=\"1\">one</li></ul>",
+        )?;
+        (item(&runtime, 1)? == first && item(&runtime, 2)? == second)
+            .then_some(())
+            .ok_or_else(|| eyre!("reordered items were recreated"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+
+pub mod value_cache {
+    use super::*;
+
+    #[wasm_bindgen_test]
+    pub async fn test_cache_is_per_runtime_and_bounded() -> Result<()> {
+        let mut runtime = runtime!()?.with_value_cache_capacity(8);
+        let app = |round: usize| "ol".children((0..4).map(|item| "li".text(round * 4 + item)));
+
+        for round in 0..5 {
+            let view = runtime.build(|| app(round).build());
+            runtime.dom_executor.rebuild(view)?;
+            (runtime.stats().cached_values <= 8)
+                .then_some(())
+                .ok_or_else(|| eyre!("cache grew past its capacity: {:?}", runtime.stats()))?;
+        }
+        runtime
+            .test()
+            .assert_contains_html("<ol><li>16</li><li>17</li><li>18</li><li>19</li></ol>")?;
+
+        let other = runtime!()?;
+        (other.stats().cached_values == 0)
+            .then_some(())
+            .ok_or_else(|| eyre!("runtimes shouldn't share a cache"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+
+pub mod detached_subtrees {
+    use super::*;
+    use wasm_bindgen::JsCast;
+    use web_sys::HtmlInputElement;
+
+    fn app(items: &[&str]) -> ElementBuilder {
+        "ul".keyed_children(
+            items.iter().copied(),
+            |item| item.to_string(),
+            |item| "li".child("input".attribute("id", item)),
+        )
+    }
+
+    fn find(runtime: &Runtime, id: &str) -> Result<HtmlInputElement> {
+        runtime
+            .root_element()
+            .as_ref()
+            .query_selector(&format!("#{id}"))
+            .map_err(|e| eyre!("{e:?}"))?
+            .ok_or_else(|| eyre!("no #{id}"))?
+            .dyn_into::<HtmlInputElement>()
+            .map_err(|e| eyre!("#{id} is not an input: {e:?}"))
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_new_children_are_inserted_in_place() -> Result<()> {
+        let mut runtime = runtime!()?;
+        runtime.dom_executor.rebuild(app(&["a", "c"]).build())?;
+        let a = find(&runtime, "a")?;
+        let c = find(&runtime, "c")?;
+
+        runtime
+            .dom_executor
+            .rebuild(app(&["a", "b", "c"]).build())?;
+        runtime.test().assert_contains_html(
+            "<ul><li><input id=\"a\"></li><li><input id=\"b\"></li><li><input id=\"c\"></li></ul>",
+        )?;
+        (find(&runtime, "a")? == a && find(&runtime, "c")? == c)
+            .then_some(())
+            .ok_or_else(|| eyre!("existing items were recreated"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_reordering_keeps_focus() -> Result<()> {
+        let mut runtime = runtime!()?;
+        runtime
+            .dom_executor
+            .rebuild(app(&["a", "b", "c"]).build())?;
+        let a = find(&runtime, "a")?;
+        a.focus().map_err(|e| eyre!("{e:?}"))?;
+
+        runtime
+            .dom_executor
+            .rebuild(app(&["c", "d", "b", "a"]).build())?;
+        runtime.test().assert_contains_html(
+            "<ul><li><input id=\"c\"></li><li><input id=\"d\"></li><li><input id=\"b\"></li><li><input id=\"a\"></li></ul>",
+        )?;
+        document()?
+            .active_element()
+            .is_some_and(|active| active == **a)
+            .then_some(())
+            .ok_or_else(|| eyre!("focus was lost while reordering"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+
+pub mod templates {
+    use super::*;
+
+    fn app(rows: &[(u32, &str, bool)]) -> ElementBuilder {
+        "table".keyed_children(
+            rows.iter().copied(),
+            |(id, _, _)| *id,
+            |(id, label, selected)| {
+                "tr".template().child("td".class("id").text(id)).child(
+                    "td".class("label")
+                        .attribute("data-selected", selected)
+                        .text(label),
+                )
+            },
+        )
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_rows_of_the_same_shape_are_cloned_and_patched() -> Result<()> {
+        let mut runtime = runtime!()?;
+        runtime
+            .dom_executor
+            .rebuild(app(&[(1, "one", true), (2, "two", false)]).build())?;
+        runtime.test().assert_contains_html(concat!(
+            "<table>",
+            "<tr><td class=\"id\">1</td><td class=\"label\" data-selected=\"\">one</td></tr>",
+            "<tr><td class=\"id\">2</td><td class=\"label\">two</td></tr>",
+            "</table>",
+        ))?;
+
+        runtime
+            .dom_executor
+            .rebuild(app(&[(1, "one", false), (2, "two", true), (3, "three", false)]).build())?;
+        runtime.test().assert_contains_html(concat!(
+            "<table>",
+            "<tr><td class=\"id\">1</td><td class=\"label\">one</td></tr>",
+            "<tr><td class=\"id\">2</td><td class=\"label\" data-selected=\"\">two</td></tr>",
+            "<tr><td class=\"id\">3</td><td class=\"label\">three</td></tr>",
+            "</table>",
+        ))?;
+        let stats = runtime.stats();
+        (stats.compiled_templates == 1 && stats.cloned_templates == 3)
+            .then_some(())
+            .ok_or_else(|| eyre!("the template wasn't compiled once and reused: {stats:?}"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_clones_keep_their_children_when_text_differs() -> Result<()> {
+        let mut runtime = runtime!()?;
+        let card = |title: &str, body: Option<&str>| {
+            "article"
+                .template()
+                .child("h2".text(title))
+                .child("section".child("p".text(body)))
+        };
+        runtime.dom_executor.rebuild(
+            "main"
+                .child(card("first", Some("one")))
+                .child(card("second", None))
+                .build(),
+        )?;
+        runtime.test().assert_contains_html(concat!(
+            "<main>",
+            "<article><h2>first</h2><section><p>one</p></section></article>",
+            "<article><h2>second</h2><section><p></p></section></article>",
+            "</main>",
+        ))?;
+        let stats = runtime.stats();
+        (stats.compiled_templates == 1 && stats.cloned_templates == 2)
+            .then_some(())
+            .ok_or_else(|| eyre!("the shape wasn't cloned: {stats:?}"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_elements_with_text_and_children_are_rejected() -> Result<()> {
+        let mut runtime = runtime!()?;
+        let rebuilt = runtime
+            .dom_executor
+            .rebuild("li".template().text("label").child("span").build());
+        matches!(
+            rebuilt,
+            Err(korvin_core::RuntimeError::TemplateTextWithChildren { .. })
+        )
+        .then_some(())
+        .ok_or_else(|| eyre!("unexpected result: {rebuilt:?}"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
+
+pub mod element_pool {
+    use super::*;
+    use web_sys::MouseEvent;
+
+    fn app(items: std::ops::Range<u32>, class: Option<&str>) -> ElementBuilder {
+        "ul".keyed_children(
+            items,
+            |id| *id,
+            |id| {
+                "li".attribute("data-id", id)
+                    .attribute("class", class)
+                    .event(id, "click", |_: MouseEvent| {})
+                    .text(id)
+            },
+        )
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_removed_elements_are_reset_and_reused() -> Result<()> {
+        let mut runtime = runtime!()?.with_element_pool(2);
+        runtime
+            .dom_executor
+            .rebuild(app(0..3, Some("old")).build())?;
+        let before = korvin_core::stats::thread();
+
+        runtime.dom_executor.rebuild(app(0..0, None).build())?;
+        let emptied = korvin_core::stats::thread();
+        let pooled = runtime.stats().pooled_elements;
+        (pooled == 2
+            && emptied.live_closures + 3 == before.live_closures
+            && emptied.live_elements + 1 == before.live_elements)
+            .then_some(())
+            .ok_or_else(|| eyre!("unexpected stats: {before:?} -> {emptied:?}, {pooled} pooled"))?;
+
+        runtime.dom_executor.rebuild(app(5..8, None).build())?;
+        runtime.test().assert_contains_html(concat!(
+            "<ul>",
+            "<li data-id=\"5\">5</li>",
+            "<li data-id=\"6\">6</li>",
+            "<li data-id=\"7\">7</li>",
+            "</ul>",
+        ))?;
+        let refilled = runtime.stats();
+        (refilled.pooled_elements == 0 && refilled.reused_elements == 2)
+            .then_some(())
+            .ok_or_else(|| eyre!("pooled elements weren't reused: {refilled:?}"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+
+    #[wasm_bindgen_test]
+    pub async fn test_elements_removed_in_a_rebuild_are_reused_in_the_same_rebuild() -> Result<()> {
+        let mut runtime = runtime!()?.with_element_pool(2);
+        runtime
+            .dom_executor
+            .rebuild(app(0..3, Some("old")).build())?;
+
+        // every key changes, the removed items are recycled before the new ones are built
+        runtime.dom_executor.rebuild(app(3..6, None).build())?;
+        runtime.test().assert_contains_html(concat!(
+            "<ul>",
+            "<li data-id=\"3\">3</li>",
+            "<li data-id=\"4\">4</li>",
+            "<li data-id=\"5\">5</li>",
+            "</ul>",
+        ))?;
+        let stats = runtime.stats();
+        (stats.reused_elements == 2 && stats.pooled_elements == 0)
+            .then_some(())
+            .ok_or_else(|| eyre!("removed elements weren't reused: {stats:?}"))?;
+        wait_for_logs().await;
+        Ok(())
+    }
+}
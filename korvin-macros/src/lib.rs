@@ -0,0 +1,206 @@
+//! `html!` writes [`ElementBuilder`] trees with HTML-like syntax, it expands to the same
+//! `AsElementBuilder` calls one would write by hand:
+//!
+//! ```ignore
+//! html! {
+//!     <ul class="todos" data-count={todos.len()}>
+//!         for todo in todos.iter() {
+//!             <li key={todo.id} class:done={todo.done} on:click[todo.id]={toggle(todo.id)}>
+//!                 {todo.title.as_str()}
+//!             </li>
+//!         }
+//!         if todos.is_empty() {
+//!             <li>"nothing to do"</li>
+//!         }
+//!         @footer(&communicator)
+//!     </ul>
+//! }
+//! ```
+//!
+//! - `name="value"` or `name={expr}` sets an attribute, `key={expr}` sets the element's key
+//! - `on:event={handler}` adds an event listener, keyed by `()` unless a key is given in
+//!   brackets: `on:event[key]={handler}`
+//! - `class:name={bool}`, `style:property={value}`, `prop:name={value}`, `bind:value={value}`
+//!   and `bind:checked={bool}` map to `class_if`, `style`, `property`, `input_value` and
+//!   `checked`
+//! - `"text"` or `{expr}` sets the text, an element has either text or child elements
+//! - `@function(args)` or `@{expr}` inserts anything that converts into an `ElementBuilder`
+//! - `if`/`else if`/`else` and `for pattern in iterator` wrap further children
+//!
+//! [`ElementBuilder`]: ../korvin_core/element_builder/struct.ElementBuilder.html
+
+use node::{Attribute, Element, InCrate, Node, Root};
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::Ident;
+
+mod node;
+
+#[proc_macro]
+pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let krate = quote! { ::korvin_core };
+    expand(syn::parse::<Root>(input).map(|root| (krate, root)))
+}
+
+/// `html!` with the path korvin_core is reachable at: `html_in!($crate; <div>...</div>)`.
+/// `korvin_core::html!` expands to it, so that the macro also works through re-exports like
+/// `korvin::core`.
+#[doc(hidden)]
+#[proc_macro]
+pub fn html_in(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand(syn::parse::<InCrate>(input).map(|InCrate(krate, root)| (krate, root)))
+}
+
+fn expand(parsed: syn::Result<(TokenStream, Root)>) -> proc_macro::TokenStream {
+    let expanded = parsed.and_then(|(krate, Root(element))| Expander { krate }.element(&element));
+    match expanded {
+        Ok(tokens) => tokens.into(),
+        // braced, a combined error expands to several `compile_error!`s
+        Err(error) => {
+            let errors = error.to_compile_error();
+            quote! {{ #errors }}.into()
+        }
+    }
+}
+
+struct Expander {
+    /// path to korvin_core
+    krate: TokenStream,
+}
+
+impl Expander {
+    fn builder_trait(&self) -> TokenStream {
+        let krate = &self.krate;
+        quote! {
+            <#krate::element_builder::ElementBuilder
+                as #krate::element_builder::AsElementBuilder>
+        }
+    }
+
+    fn attribute(&self, builder: &Ident, attribute: &Attribute) -> TokenStream {
+        let methods = self.builder_trait();
+        match attribute {
+            Attribute::Plain { name, value } => {
+                let name = name.lit();
+                quote_spanned! {name.span()=> #methods::attribute(#builder, #name, #value) }
+            }
+            Attribute::Key { value } => quote! { #methods::key(#builder, #value) },
+            Attribute::Event { name, key, handler } => {
+                let name = name.lit();
+                let key = key
+                    .as_ref()
+                    .map(|key| quote! { #key })
+                    .unwrap_or(quote! { () });
+                quote_spanned! {name.span()=> #methods::event(#builder, #key, #name, #handler) }
+            }
+            Attribute::Class { name, enabled } => {
+                let name = name.lit();
+                quote_spanned! {name.span()=> #methods::class_if(#builder, #name, #enabled) }
+            }
+            Attribute::Style { name, value } => {
+                let name = name.lit();
+                quote_spanned! {name.span()=> #methods::style(#builder, #name, #value) }
+            }
+            Attribute::Property { name, value } => {
+                let name = name.lit();
+                quote_spanned! {name.span()=> #methods::property(#builder, #name, #value) }
+            }
+            Attribute::Bind { name, value } => match name.value.as_str() {
+                "checked" => quote_spanned! {name.span=> #methods::checked(#builder, #value) },
+                _ => quote_spanned! {name.span=> #methods::input_value(#builder, #value) },
+            },
+        }
+    }
+
+    /// Statements pushing the children built by `nodes` into `children`.
+    fn children(&self, children: &Ident, nodes: &[Node]) -> syn::Result<TokenStream> {
+        let krate = &self.krate;
+        nodes
+            .iter()
+            .map(|node| match node {
+                Node::Element(element) => {
+                    let element = self.element(element)?;
+                    Ok(quote! { #children.push(#element); })
+                }
+                Node::Component(component) => Ok(quote! {
+                    #children.push(::core::convert::Into::<
+                        #krate::element_builder::ElementBuilder,
+                    >::into(#component));
+                }),
+                Node::Text(_) => Err(syn::Error::new(
+                    node.span(),
+                    "text can't be mixed with child elements, wrap it in an element, e.g. <span>",
+                )),
+                Node::If {
+                    branches,
+                    otherwise,
+                } => {
+                    let branches = branches
+                        .iter()
+                        .map(|branch| {
+                            let condition = &branch.condition;
+                            let nodes = self.children(children, &branch.nodes)?;
+                            Ok(quote! { if #condition { #nodes } })
+                        })
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    let otherwise = otherwise
+                        .as_ref()
+                        .map(|nodes| {
+                            self.children(children, nodes)
+                                .map(|nodes| quote! { else { #nodes } })
+                        })
+                        .transpose()?;
+                    Ok(quote! { #(#branches)else* #otherwise })
+                }
+                Node::For {
+                    pattern,
+                    iterator,
+                    nodes,
+                } => {
+                    let nodes = self.children(children, nodes)?;
+                    Ok(quote! { for #pattern in #iterator { #nodes } })
+                }
+            })
+            .collect()
+    }
+
+    fn element(&self, element: &Element) -> syn::Result<TokenStream> {
+        let krate = &self.krate;
+        let methods = self.builder_trait();
+        // mixed site, so that the locals can't shadow variables used inside the macro
+        let builder = Ident::new("builder", Span::mixed_site());
+        let children = Ident::new("children", Span::mixed_site());
+        let kind = element.name.lit();
+        let attributes = element
+            .attributes
+            .iter()
+            .map(|attribute| self.attribute(&builder, attribute));
+
+        let text = match element.children.as_slice() {
+            [Node::Text(text)] => Some(text),
+            _ => None,
+        };
+        let body = match text {
+            Some(text) => quote! { #methods::text(#builder, #text) },
+            None if element.children.is_empty() => quote! { #builder },
+            None => {
+                let pushes = self.children(&children, &element.children)?;
+                quote! {
+                    {
+                        let mut #children =
+                            ::std::vec::Vec::<#krate::element_builder::ElementBuilder>::new();
+                        #pushes
+                        #methods::children(#builder, #children)
+                    }
+                }
+            }
+        };
+        Ok(quote! {
+            {
+                let #builder = #methods::builder(#kind);
+                #(let #builder = #attributes;)*
+                #body
+            }
+        })
+    }
+}
@@ -0,0 +1,388 @@
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::{
+    braced, bracketed,
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token, Expr, Ident, LitStr, Pat, Path, Result, Token,
+};
+
+/// Hyphenated name, `data-id`, `aria-label` or `my-element`. Keywords are allowed so that
+/// `for` and `type` can be used as attribute names.
+#[derive(Clone)]
+pub struct Name {
+    pub value: String,
+    pub span: Span,
+}
+
+impl Parse for Name {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let first = Ident::parse_any(input)?;
+        let mut value = first.to_string();
+        let mut span = first.span();
+        while input.peek(Token![-]) {
+            let dash = input.parse::<Token![-]>()?;
+            let next = Ident::parse_any(input)?;
+            value.push('-');
+            value.push_str(&next.to_string());
+            span = span
+                .join(dash.span)
+                .and_then(|s| s.join(next.span()))
+                .unwrap_or(span);
+        }
+        Ok(Self { value, span })
+    }
+}
+
+impl Name {
+    pub fn lit(&self) -> LitStr {
+        LitStr::new(&self.value, self.span)
+    }
+}
+
+pub enum Value {
+    Literal(LitStr),
+    Expr(Expr),
+}
+
+impl Parse for Value {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            return input.parse().map(Self::Literal);
+        }
+        if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            return content.parse().map(Self::Expr);
+        }
+        Err(input.error("expected a string literal or an expression in braces: `{...}`"))
+    }
+}
+
+impl ToTokens for Value {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Literal(literal) => literal.to_tokens(tokens),
+            Self::Expr(expr) => expr.to_tokens(tokens),
+        }
+    }
+}
+
+pub enum Attribute {
+    /// `name="value"` or `name={expr}`
+    Plain { name: Name, value: Value },
+    /// `key={expr}`
+    Key { value: Value },
+    /// `on:click={handler}`, or `on:click[key]={handler}` to replace the listener when `key`
+    /// changes
+    Event {
+        name: Name,
+        key: Option<Expr>,
+        handler: Value,
+    },
+    /// `class:active={bool}`
+    Class { name: Name, enabled: Value },
+    /// `style:color={value}`
+    Style { name: Name, value: Value },
+    /// `prop:muted={value}`
+    Property { name: Name, value: Value },
+    /// `bind:value={value}` or `bind:checked={bool}`
+    Bind { name: Name, value: Value },
+}
+
+impl Parse for Attribute {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse::<Name>()?;
+        if input.peek(Token![:]) && !input.peek(Token![::]) {
+            input.parse::<Token![:]>()?;
+            let target = input.parse::<Name>()?;
+            let key = match (name.value.as_str(), input.peek(token::Bracket)) {
+                ("on", true) => {
+                    let content;
+                    bracketed!(content in input);
+                    Some(content.parse::<Expr>()?)
+                }
+                _ => None,
+            };
+            input.parse::<Token![=]>()?;
+            let value = input.parse::<Value>()?;
+            return match name.value.as_str() {
+                "on" => Ok(Self::Event {
+                    name: target,
+                    key,
+                    handler: value,
+                }),
+                "class" => Ok(Self::Class {
+                    name: target,
+                    enabled: value,
+                }),
+                "style" => Ok(Self::Style {
+                    name: target,
+                    value,
+                }),
+                "prop" => Ok(Self::Property {
+                    name: target,
+                    value,
+                }),
+                "bind" => match target.value.as_str() {
+                    "value" | "checked" => Ok(Self::Bind {
+                        name: target,
+                        value,
+                    }),
+                    _ => Err(syn::Error::new(
+                        target.span,
+                        "only `bind:value` and `bind:checked` are supported",
+                    )),
+                },
+                _ => Err(syn::Error::new(
+                    name.span,
+                    "unknown attribute prefix, expected one of `on:`, `class:`, `style:`, \
+                     `prop:` or `bind:`",
+                )),
+            };
+        }
+        input.parse::<Token![=]>()?;
+        let value = input.parse::<Value>()?;
+        Ok(match name.value.as_str() {
+            "key" => Self::Key { value },
+            _ => Self::Plain { name, value },
+        })
+    }
+}
+
+pub struct Element {
+    pub name: Name,
+    pub attributes: Vec<Attribute>,
+    pub children: Vec<Node>,
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name = input.parse::<Name>()?;
+        let mut attributes = vec![];
+        loop {
+            if input.peek(Token![/]) {
+                input.parse::<Token![/]>()?;
+                input.parse::<Token![>]>()?;
+                return Ok(Self {
+                    name,
+                    attributes,
+                    children: vec![],
+                });
+            }
+            if input.peek(Token![>]) {
+                input.parse::<Token![>]>()?;
+                break;
+            }
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    name.span,
+                    format!("unclosed opening tag <{}>", name.value),
+                ));
+            }
+            attributes.push(input.parse()?);
+        }
+        let mut children = vec![];
+        loop {
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    name.span,
+                    format!("missing closing tag </{}>", name.value),
+                ));
+            }
+            if input.peek(Token![<]) && input.peek2(Token![/]) {
+                input.parse::<Token![<]>()?;
+                input.parse::<Token![/]>()?;
+                let closing = input.parse::<Name>()?;
+                input.parse::<Token![>]>()?;
+                if closing.value != name.value {
+                    let mut error = syn::Error::new(
+                        closing.span,
+                        format!(
+                            "mismatched closing tag, expected </{}>, found </{}>",
+                            name.value, closing.value
+                        ),
+                    );
+                    error.combine(syn::Error::new(
+                        name.span,
+                        format!("<{}> opened here", name.value),
+                    ));
+                    return Err(error);
+                }
+                break;
+            }
+            children.push(input.parse()?);
+        }
+        Ok(Self {
+            name,
+            attributes,
+            children,
+        })
+    }
+}
+
+pub struct Branch {
+    pub condition: Expr,
+    pub nodes: Vec<Node>,
+}
+
+pub enum Node {
+    Element(Element),
+    /// `"literal"` or `{expr}`, sets the element's text
+    Text(Value),
+    /// `@component(args)` or `@{expr}`, anything that converts into an `ElementBuilder`
+    Component(Expr),
+    If {
+        branches: Vec<Branch>,
+        otherwise: Option<Vec<Node>>,
+    },
+    For {
+        pattern: Box<Pat>,
+        iterator: Box<Expr>,
+        nodes: Vec<Node>,
+    },
+}
+
+fn nodes_in_braces(input: ParseStream) -> Result<Vec<Node>> {
+    let content;
+    braced!(content in input);
+    let mut nodes = vec![];
+    while !content.is_empty() {
+        nodes.push(content.parse()?);
+    }
+    Ok(nodes)
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![<]) {
+            if input.peek2(Token![/]) {
+                return Err(input.error("unexpected closing tag"));
+            }
+            return input.parse().map(Self::Element);
+        }
+        if input.peek(LitStr) || input.peek(token::Brace) {
+            return input.parse().map(Self::Text);
+        }
+        if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            if input.peek(token::Brace) {
+                let content;
+                braced!(content in input);
+                return content.parse().map(Self::Component);
+            }
+            let function = Path::parse_mod_style(input)?;
+            let content;
+            let paren = parenthesized!(content in input);
+            let args = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+            return Ok(Self::Component(Expr::Call(syn::ExprCall {
+                attrs: vec![],
+                func: Box::new(Expr::Path(syn::ExprPath {
+                    attrs: vec![],
+                    qself: None,
+                    path: function,
+                })),
+                paren_token: paren,
+                args,
+            })));
+        }
+        if input.peek(Token![if]) {
+            let mut branches = vec![];
+            let mut otherwise = None;
+            loop {
+                input.parse::<Token![if]>()?;
+                let condition = Expr::parse_without_eager_brace(input)?;
+                let nodes = nodes_in_braces(input)?;
+                branches.push(Branch { condition, nodes });
+                if !input.peek(Token![else]) {
+                    break;
+                }
+                input.parse::<Token![else]>()?;
+                if !input.peek(Token![if]) {
+                    otherwise = Some(nodes_in_braces(input)?);
+                    break;
+                }
+            }
+            return Ok(Self::If {
+                branches,
+                otherwise,
+            });
+        }
+        if input.peek(Token![for]) {
+            input.parse::<Token![for]>()?;
+            let pattern = Box::new(Pat::parse_multi_with_leading_vert(input)?);
+            input.parse::<Token![in]>()?;
+            let iterator = Box::new(Expr::parse_without_eager_brace(input)?);
+            let nodes = nodes_in_braces(input)?;
+            return Ok(Self::For {
+                pattern,
+                iterator,
+                nodes,
+            });
+        }
+        Err(input.error(
+            "expected an element `<tag>`, text `\"...\"` or `{...}`, a component `@name(...)`, \
+             `if` or `for`",
+        ))
+    }
+}
+
+impl Node {
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Element(element) => element.name.span,
+            Self::Text(Value::Literal(literal)) => literal.span(),
+            Self::Text(Value::Expr(expr)) | Self::Component(expr) => expr.span(),
+            Self::If { branches, .. } => branches
+                .first()
+                .map(|branch| branch.condition.span())
+                .unwrap_or_else(Span::call_site),
+            Self::For { iterator, .. } => iterator.span(),
+        }
+    }
+}
+
+/// Everything passed to `html!`, it must be exactly one element.
+pub struct Root(pub Element);
+
+impl Parse for Root {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.is_empty() {
+            return Err(input.error("expected a root element"));
+        }
+        let element = input.parse::<Element>()?;
+        if !input.is_empty() {
+            return Err(input.error(
+                "`html!` expects a single root element, wrap siblings in a parent element",
+            ));
+        }
+        Ok(Self(element))
+    }
+}
+
+/// `path; <root>`, the path korvin_core can be reached at (usually `$crate`) and the root.
+pub struct InCrate(pub TokenStream, pub Root);
+
+impl Parse for InCrate {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let krate = input.step(|cursor| {
+            let mut krate = TokenStream::new();
+            let mut rest = *cursor;
+            while let Some((tree, next)) = rest.token_tree() {
+                match &tree {
+                    proc_macro2::TokenTree::Punct(punct) if punct.as_char() == ';' => {
+                        return Ok((krate, next));
+                    }
+                    _ => krate.extend([tree]),
+                }
+                rest = next;
+            }
+            Err(cursor.error("expected the path to korvin_core followed by `;`"))
+        })?;
+        Ok(Self(krate, input.parse()?))
+    }
+}
@@ -1,11 +1,15 @@
 use super::*;
-use korvin_core::{element_builder::ElementBuilder, web_sys::MouseEvent};
+use korvin_core::{
+    flavors::elm_like::html::Html,
+    web_sys::{InputEvent, MouseEvent},
+};
 
+#[derive(Debug)]
 pub enum CounterMessage {
     Increment,
     SetCount(i32),
 }
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Counter {
     count: i32,
 }
@@ -19,28 +23,27 @@ impl HandleMessage<CounterMessage> for Counter {
     }
 }
 
-impl ToLazyHtml for WithCommunicator<Counter, CounterMessage> {
-    fn to_lazy_html(&self) -> ElementBuilder {
-        let Self {
-            inner,
-            communicator,
-        } = self;
-        let communicator = *communicator;
-        "main"
+impl Counter {
+    /// handlers return messages, so this view can be embedded anywhere with [`Html::map`]
+    pub fn html(&self) -> Html<CounterMessage> {
+        Html::from("main")
             .child("h3".text("7 GUIs: Counter"))
             .attribute("class", "counter")
-            .child(input(
+            .child(Html::from("input").input_value(self.count).on_maybe(
                 (),
-                communicator,
-                inner.count,
-                CounterMessage::SetCount,
+                "input",
+                |event: InputEvent| match event.value() {
+                    Ok(count) => Some(CounterMessage::SetCount(count)),
+                    Err(message) => {
+                        tracing::error!(?message, "bad input value");
+                        None
+                    }
+                },
             ))
             .child(
-                "button"
+                Html::from("button")
                     .text("Count")
-                    .event((), "mousedown", move |_: MouseEvent| {
-                        communicator.send(CounterMessage::Increment)
-                    }),
+                    .on((), "mousedown", |_: MouseEvent| CounterMessage::Increment),
             )
     }
 }
@@ -1,7 +1,8 @@
-use crate::utils::InputEventExt;
-
 use super::*;
-use korvin_core::{element_builder::ElementBuilder, web_sys::InputEvent};
+use korvin_core::{
+    element_builder::ElementBuilder,
+    flavors::elm_like::{form::Field, html::Html},
+};
 
 impl HandleMessage<TemperatureConverterMessage> for TemperatureConverter {
     fn handle(&mut self, message: TemperatureConverterMessage) {
@@ -10,71 +11,77 @@ impl HandleMessage<TemperatureConverterMessage> for TemperatureConverter {
 }
 
 pub enum TemperatureConverterMessage {
-    FahrenheitChanged(f32),
-    CelciusChanged(f32),
+    FahrenheitChanged(String),
+    CelciusChanged(String),
 }
 
-#[derive(Default)]
 pub struct TemperatureConverter {
-    fahrenheit: f32,
-    celcius: f32,
+    fahrenheit: Field<f32>,
+    celcius: Field<f32>,
+}
+
+impl Default for TemperatureConverter {
+    fn default() -> Self {
+        Self {
+            fahrenheit: Field::with_value(32.),
+            celcius: Field::with_value(0.),
+        }
+    }
 }
 
 fn handle_message(state: &mut TemperatureConverter, message: TemperatureConverterMessage) {
     match message {
         TemperatureConverterMessage::FahrenheitChanged(fahrenheit) => {
-            state.fahrenheit = fahrenheit;
-            state.celcius = (fahrenheit - 32.) * (5. / 9.)
+            state.fahrenheit.set(fahrenheit);
+            if let Some(fahrenheit) = state.fahrenheit.value() {
+                state
+                    .celcius
+                    .set(((fahrenheit - 32.) * (5. / 9.)).to_string())
+            }
         }
         TemperatureConverterMessage::CelciusChanged(celcius) => {
-            state.celcius = celcius;
-            state.fahrenheit = celcius * (9. / 5.) + 32.;
+            state.celcius.set(celcius);
+            if let Some(celcius) = state.celcius.value() {
+                state
+                    .fahrenheit
+                    .set((celcius * (9. / 5.) + 32.).to_string())
+            }
         }
     }
 }
 
-fn labeled_input(value: f32, label: &str) -> ElementBuilder {
-    "span"
+fn labeled_input(
+    field: &Field<f32>,
+    label: &'static str,
+    message: fn(String) -> TemperatureConverterMessage,
+) -> Html<TemperatureConverterMessage> {
+    Html::from("span")
         .attribute("class", label)
-        .child("input".input_value(value.to_string().as_str()))
+        .child(field.input((), message))
         .text(label)
+        .child(field.errors_list())
         .key(label)
 }
 
-fn app(
-    communicator: Communicator<TemperatureConverterMessage>,
-    inner: &TemperatureConverter,
-) -> ElementBuilder {
-    let on_fahrenheit_changed = move |input: InputEvent| match input.value() {
-        Ok(v) => communicator.send(TemperatureConverterMessage::FahrenheitChanged(v)),
-        Err(message) => {
-            tracing::error!(?message, "bad input");
-        }
-    };
-    let on_celcius_changed = move |input: InputEvent| match input.value() {
-        Ok(v) => communicator.send(TemperatureConverterMessage::CelciusChanged(v)),
-        Err(message) => {
-            tracing::error!(?message, "bad input");
-        }
-    };
-    "main"
+fn app(inner: &TemperatureConverter) -> Html<TemperatureConverterMessage> {
+    Html::from("main")
         .attribute("class", "temperature-converter")
         .child("h3".text("7 GUIs: Temperature Converter"))
-        .child(labeled_input(inner.fahrenheit, "fahrenheit").event(
-            (),
-            "input",
-            on_fahrenheit_changed,
+        .child(labeled_input(
+            &inner.fahrenheit,
+            "fahrenheit",
+            TemperatureConverterMessage::FahrenheitChanged,
         ))
         .child("span".text(" = "))
-        .child(labeled_input(inner.celcius, "celcius").event((), "input", on_celcius_changed))
+        .child(labeled_input(
+            &inner.celcius,
+            "celcius",
+            TemperatureConverterMessage::CelciusChanged,
+        ))
 }
 
-impl ToLazyHtml for WithCommunicator<TemperatureConverter, TemperatureConverterMessage> {
-    fn to_lazy_html(&self) -> ElementBuilder {
-        let Self {
-            inner,
-            communicator,
-        } = self;
-        app(*communicator, inner)
+impl View<TemperatureConverterMessage> for TemperatureConverter {
+    fn view(&self, communicator: Communicator<TemperatureConverterMessage>) -> ElementBuilder {
+        app(self).render(&communicator)
     }
 }
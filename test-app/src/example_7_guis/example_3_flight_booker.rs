@@ -1,6 +1,6 @@
 use super::*;
 use chrono::NaiveDate;
-use korvin_core::{element_builder::ElementBuilder, web_sys::MouseEvent};
+use korvin_core::{element_builder::ElementBuilder, flavors::elm_like::select, html};
 
 pub enum FlightBookerMessage {
     SetMode(FlightBookerMode),
@@ -52,69 +52,56 @@ fn app(communicator: Communicator<FlightBookerMessage>, inner: &FlightBooker) ->
             FlightBookerMode::OneWayFlight { start: _ } => None,
             FlightBookerMode::ReturnFlight { start: _, end } => Some(end),
         };
-        let select = "select"
-            .attribute(
-                "value",
-                match inner.mode {
-                    FlightBookerMode::OneWayFlight { .. } => one_way_flight,
-                    FlightBookerMode::ReturnFlight { .. } => return_flight,
-                },
-            )
-            .child(
-                "option"
-                    .attribute("value", one_way_flight)
-                    .text(one_way_flight)
-                    .event((start, end), "mousedown", move |_: MouseEvent| {
-                        communicator.send(FlightBookerMessage::SetMode(
-                            FlightBookerMode::OneWayFlight { start },
-                        ))
-                    }),
-            )
-            .child(
-                "option"
-                    .attribute("value", return_flight)
-                    .text(return_flight)
-                    .event((start, end), "click", move |_: MouseEvent| {
-                        communicator.send(FlightBookerMessage::SetMode(
-                            FlightBookerMode::ReturnFlight {
-                                start,
-                                end: end.unwrap_or_else(today),
-                            },
-                        ))
-                    }),
-            );
+        let select = select(
+            (start, end),
+            &communicator,
+            [
+                (one_way_flight, one_way_flight),
+                (return_flight, return_flight),
+            ],
+            match inner.mode {
+                FlightBookerMode::OneWayFlight { .. } => one_way_flight,
+                FlightBookerMode::ReturnFlight { .. } => return_flight,
+            },
+            move |mode| {
+                FlightBookerMessage::SetMode(match mode == return_flight {
+                    true => FlightBookerMode::ReturnFlight {
+                        start,
+                        end: end.unwrap_or_else(today),
+                    },
+                    false => FlightBookerMode::OneWayFlight { start },
+                })
+            },
+        );
         let inputs = {
             let container = "div";
             match inner.mode {
                 FlightBookerMode::OneWayFlight { start } => {
-                    container.child(input((), communicator, start, |start| {
+                    container.child(input((), &communicator, start, |start| {
                         FlightBookerMessage::SetMode(FlightBookerMode::OneWayFlight { start })
                     }))
                 }
                 FlightBookerMode::ReturnFlight { start, end } => container
-                    .child(input(end, communicator, start, move |start| {
+                    .child(input(end, &communicator, start, move |start| {
                         FlightBookerMessage::SetMode(FlightBookerMode::ReturnFlight { start, end })
                     }))
-                    .child(input(start, communicator, end, move |end| {
+                    .child(input(start, &communicator, end, move |end| {
                         FlightBookerMessage::SetMode(FlightBookerMode::ReturnFlight { start, end })
                     })),
             }
         };
         "div".child(select).child(inputs)
     };
-    "main"
-        .attribute("class", "flight-booker")
-        .child("h3".text("7 GUIs: Flight Booker"))
-        .child("div".child(body))
-        .child("div".text(format!("{inner:#?}").as_str()))
+    html::main()
+        .class("flight-booker")
+        .child(html::h3().text("7 GUIs: Flight Booker"))
+        .child(html::div().child(body))
+        .child(html::div().text(format!("{inner:#?}")))
+        .into()
 }
 
-impl ToLazyHtml for WithCommunicator<FlightBooker, FlightBookerMessage> {
-    fn to_lazy_html(&self) -> ElementBuilder {
-        let Self {
-            inner,
-            communicator,
-        } = self;
-        app(*communicator, inner)
+impl View<FlightBookerMessage> for FlightBooker {
+    fn view(&self, communicator: Communicator<FlightBookerMessage>) -> ElementBuilder {
+        app(communicator, self)
     }
 }
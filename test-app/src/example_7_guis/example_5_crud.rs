@@ -71,7 +71,7 @@ impl HandleMessage<CrudMessage> for Crud {
 }
 
 fn form(
-    communicator: Communicator<CrudMessage>,
+    communicator: &Communicator<CrudMessage>,
     user: User,
     message: impl Fn(User) -> CrudMessage + Clone + 'static,
     button_text: &str,
@@ -113,20 +113,16 @@ fn form(
     "div".child(first_name).child(last_name).child(submit)
 }
 
-impl ToLazyHtml for WithCommunicator<Crud, CrudMessage> {
-    fn to_lazy_html(&self) -> ElementBuilder {
-        let Self {
-            inner,
-            communicator,
-        } = self;
-        let communicator = *communicator;
+impl View<CrudMessage> for Crud {
+    fn view(&self, communicator: Communicator<CrudMessage>) -> ElementBuilder {
+        let inner = self;
         let user_form = {
             let container = "div".attribute("class", "form");
 
             match inner.user_form.clone() {
                 UserForm::New(user) => container.child(
                     form(
-                        communicator,
+                        &communicator,
                         user.clone(),
                         CrudMessage::CreateUser,
                         "create",
@@ -136,7 +132,7 @@ impl ToLazyHtml for WithCommunicator<Crud, CrudMessage> {
                 UserForm::Edit(idx, user) => container
                     .child(
                         form(
-                            communicator,
+                            &communicator,
                             user.clone(),
                             move |user| CrudMessage::UpdateUser(idx, user),
                             "update",
@@ -144,8 +140,10 @@ impl ToLazyHtml for WithCommunicator<Crud, CrudMessage> {
                         .key("edit"),
                     )
                     .child(
-                        button("delete", communicator, move || CrudMessage::DeleteUser(idx))
-                            .text("delete"),
+                        button("delete", &communicator, move || {
+                            CrudMessage::DeleteUser(idx)
+                        })
+                        .text("delete"),
                     ),
             }
         };
@@ -157,8 +155,11 @@ impl ToLazyHtml for WithCommunicator<Crud, CrudMessage> {
                         .input_value(inner.filter.as_str())
                         .attribute("id", "filter")
                         .attribute("name", "filter")
-                        .event((), "input", move |e: InputEvent| {
-                            e.on_value(communicator, CrudMessage::UpdateFilter)
+                        .event((), "input", {
+                            let communicator = communicator.clone();
+                            move |e: InputEvent| {
+                                e.on_value(&communicator, CrudMessage::UpdateFilter)
+                            }
                         }),
                 )
         };
@@ -195,6 +196,7 @@ impl ToLazyHtml for WithCommunicator<Crud, CrudMessage> {
                                 )| {
                                     let display = format!("{last_name}, {first_name}");
                                     let user = user.clone();
+                                    let communicator = communicator.clone();
                                     "option"
                                         .text(display.as_str())
                                         .attribute("value", display.as_str())
@@ -207,6 +209,6 @@ impl ToLazyHtml for WithCommunicator<Crud, CrudMessage> {
                             ),
                     ),
             )
-            .child("div".text(format!("{inner:#?}").as_str()))
+            .child("div".text(format!("{inner:#?}")))
     }
 }
@@ -1,13 +1,11 @@
-use eyre::{eyre, Result};
-use futures_util::{Stream, StreamExt};
+use eyre::Result;
 use korvin_core::{
     element_builder::AsElementBuilder,
+    flavors::elm_like::{cmd::Cmd, program::Program},
     web_sys::{self},
-    Runtime,
 };
 use tracing_subscriber::fmt::format::Pretty;
-use utils::{button, input, Communicator, InputEventExt, WithCommunicatorExt};
-use utils::{HandleMessage, ToLazyHtml, WithCommunicator, WithEventHandling};
+use utils::{button, input, Communicator, HandleMessage, InputEventExt, View};
 
 pub mod utils;
 
@@ -45,43 +43,17 @@ fn setup_logging() -> Result<()> {
     Ok(())
 }
 
-impl<T, M> WithEventHandling<T, M> {
-    fn run(self) -> Result<()>
-    where
-        M: 'static,
-        T: HandleMessage<M> + 'static,
-        WithCommunicator<T, M>: ToLazyHtml,
-    {
-        let body = web_sys::window()
-            .and_then(|w| w.document())
-            .and_then(|d| d.body())
-            .ok_or_else(|| eyre!("no body"))?;
-        wasm_bindgen_futures::spawn_local(async move {
-            let mut runtime = Runtime::new(body);
-
-            let Self {
-                mut with_communicator,
-                mut rx,
-            } = self;
+fn example<T, M>() -> Program<T, M>
+where
+    T: Default + HandleMessage<M> + View<M> + 'static,
+    M: 'static,
+{
+    Program::new(T::default, update, T::view)
+}
 
-            if let Err(message) = runtime
-                .dom_executor
-                .rebuild(with_communicator.to_lazy_html().build())
-            {
-                tracing::error!(?message, "rebuilding failed");
-            }
-            while let Some(message) = rx.next().await {
-                with_communicator.inner.handle(message);
-                if let Err(message) = runtime
-                    .dom_executor
-                    .rebuild(with_communicator.to_lazy_html().build())
-                {
-                    tracing::error!(?message, "rebuilding failed");
-                }
-            }
-        });
-        Ok(())
-    }
+fn update<T: HandleMessage<M>, M: 'static>(model: &mut T, message: M) -> Cmd<M> {
+    model.handle(message);
+    Cmd::none()
 }
 
 pub mod example_7_guis;
@@ -98,26 +70,23 @@ pub async fn sleep_ms(ms: i32) {
         .expect("sleep failed");
 }
 
-fn interval(interval: i32) -> impl Stream<Item = ()> {
-    futures::stream::repeat(()).then(move |()| sleep_ms(interval))
-}
-
 pub fn app() -> Result<()> {
+    use example_7_guis::*;
     setup_logging()?;
-    example_7_guis::example_1_counter::Counter::default()
-        .with_example_event_handling()
-        .run()?;
-    example_7_guis::example_2_temperature_converter::TemperatureConverter::default()
-        .with_example_event_handling()
-        .run()?;
-    example_7_guis::example_3_flight_booker::FlightBooker::default()
-        .with_example_event_handling()
-        .run()?;
-    // custom runtime example
-    example_7_guis::example_4_timer::run()?;
-    example_7_guis::example_5_crud::Crud::default()
-        .with_example_event_handling()
-        .run()?;
+    let counter = Program::html(
+        example_1_counter::Counter::default,
+        update,
+        example_1_counter::Counter::html,
+    );
+    #[cfg(debug_assertions)]
+    let counter = counter.debugger();
+    counter.mount("body")?;
+    example::<example_2_temperature_converter::TemperatureConverter, _>().mount("body")?;
+    example::<example_3_flight_booker::FlightBooker, _>().mount("body")?;
+    example::<example_4_timer::Timer, _>()
+        .subscriptions(example_4_timer::subscriptions)
+        .mount("body")?;
+    example::<example_5_crud::Crud, _>().mount("body")?;
 
     Ok(())
 }
@@ -1,5 +1,4 @@
 use eyre::{eyre, ContextCompat, Result, WrapErr};
-use futures::channel::mpsc::UnboundedReceiver;
 pub use korvin_core::flavors::elm_like::Communicator;
 use korvin_core::{
     element_builder::AsElementBuilder,
@@ -48,7 +47,7 @@ pub trait InputEventExt {
     fn value<T: FromStr>(&self) -> Result<T>
     where
         <T as FromStr>::Err: std::fmt::Debug;
-    fn on_value<M, T: FromStr, F: Fn(T) -> M>(self, communicator: Communicator<M>, handle: F)
+    fn on_value<M, T: FromStr, F: Fn(T) -> M>(self, communicator: &Communicator<M>, handle: F)
     where
         <T as FromStr>::Err: std::fmt::Debug,
         T: FromStr,
@@ -72,7 +71,7 @@ impl InputEventExt for web_sys::InputEvent {
         })
     }
 
-    fn on_value<M, T, F>(self, communicator: Communicator<M>, handle: F)
+    fn on_value<M, T, F>(self, communicator: &Communicator<M>, handle: F)
     where
         <T as FromStr>::Err: std::fmt::Debug,
         T: FromStr,
@@ -86,7 +85,7 @@ impl InputEventExt for web_sys::InputEvent {
 
 pub fn input<T, M, F>(
     key: impl std::hash::Hash,
-    communicator: Communicator<M>,
+    communicator: &Communicator<M>,
     value: T,
     callback: F,
 ) -> ElementBuilder
@@ -95,63 +94,29 @@ where
     <T as FromStr>::Err: std::fmt::Debug,
     F: Fn(T) -> M + 'static + Clone,
 {
+    let communicator = communicator.clone();
     "input"
-        .input_value(value.to_string().as_str())
+        .input_value(value.to_string())
         .event(key, "input", move |event: InputEvent| {
-            event.on_value(communicator, callback.clone())
+            event.on_value(&communicator, callback.clone())
         })
 }
 pub fn button<M, F>(
     key: impl std::hash::Hash,
-    communicator: Communicator<M>,
+    communicator: &Communicator<M>,
     callback: F,
 ) -> ElementBuilder
 where
     F: (Fn() -> M) + 'static + Clone,
 {
+    let communicator = communicator.clone();
     "button".event(key, "mousedown", move |_: MouseEvent| {
         communicator.send(callback())
     })
 }
 
-pub trait ToLazyHtml {
-    fn to_lazy_html(&self) -> ElementBuilder;
-}
-
-pub struct WithCommunicator<T, M: 'static> {
-    pub inner: T,
-    pub communicator: Communicator<M>,
-}
-
-pub struct WithEventHandling<T, M: 'static> {
-    pub with_communicator: WithCommunicator<T, M>,
-    pub rx: UnboundedReceiver<M>,
-}
-
-pub trait WithCommunicatorExt: Sized {
-    fn with_communicator<M>(self) -> (UnboundedReceiver<M>, WithCommunicator<Self, M>);
-    fn with_example_event_handling<M>(self) -> WithEventHandling<Self, M>;
-}
-
-impl<T: Sized> WithCommunicatorExt for T {
-    fn with_communicator<M>(self) -> (UnboundedReceiver<M>, WithCommunicator<Self, M>) {
-        let (rx, communicator) = Communicator::create();
-        (
-            rx,
-            WithCommunicator {
-                inner: self,
-                communicator,
-            },
-        )
-    }
-
-    fn with_example_event_handling<M>(self) -> WithEventHandling<Self, M> {
-        let (rx, with_communicator) = self.with_communicator();
-        WithEventHandling {
-            with_communicator,
-            rx,
-        }
-    }
+pub trait View<M> {
+    fn view(&self, communicator: Communicator<M>) -> ElementBuilder;
 }
 
 pub trait HandleMessage<M> {